@@ -2,7 +2,7 @@ use super::AtatClient;
 use crate::{
     helpers::LossyStr,
     response_slot::{ResponseSlot, ResponseSlotGuard},
-    AtatCmd, Config, Error,
+    AtatCmd, AtatUrc, Config, Error, InternalError, UrcSubscription,
 };
 use embassy_time::{with_timeout, Duration, Instant, TimeoutError, Timer};
 use embedded_io::ErrorType;
@@ -12,12 +12,23 @@ use futures::{
     pin_mut, Future,
 };
 
+/// Client responsible for sending commands and awaiting their responses.
+///
+/// URCs are published to the [`UrcChannel`](crate::UrcChannel) by the ingress,
+/// independently of the client. A pending `send` therefore never holds back
+/// URCs received while it awaits its response.
 pub struct Client<'a, W: Write, const INGRESS_BUF_SIZE: usize> {
     writer: W,
     res_slot: &'a ResponseSlot<INGRESS_BUF_SIZE>,
     buf: &'a mut [u8],
     config: Config,
     cooldown_timer: Option<Timer>,
+    sequence: u8,
+    last_sent: Instant,
+    awaiting_response: bool,
+    tx_filter: Option<fn(&[u8], &mut [u8]) -> usize>,
+    #[cfg(feature = "last-command")]
+    last_command: heapless::Vec<u8, { crate::LAST_COMMAND_LEN }>,
 }
 
 impl<'a, W: Write, const INGRESS_BUF_SIZE: usize> Client<'a, W, INGRESS_BUF_SIZE> {
@@ -27,19 +38,147 @@ impl<'a, W: Write, const INGRESS_BUF_SIZE: usize> Client<'a, W, INGRESS_BUF_SIZE
         buf: &'a mut [u8],
         config: Config,
     ) -> Self {
+        res_slot.set_echo_enabled(config.echo_enabled);
         Self {
             writer,
             res_slot,
             buf,
             config,
             cooldown_timer: None,
+            sequence: 0,
+            last_sent: Instant::now(),
+            awaiting_response: false,
+            tx_filter: None,
+            #[cfg(feature = "last-command")]
+            last_command: heapless::Vec::new(),
         }
     }
 
+    /// Set a filter applied to every serialized command, right before it is
+    /// written to the transmit side.
+    ///
+    /// The filter is handed the serialized command, and the unused remainder
+    /// of the client buffer to write its output into. It returns the number of
+    /// output bytes, which are then transmitted instead of the command. Both
+    /// slices are owned by the client and only valid for the duration of the
+    /// call.
+    ///
+    /// Sending fails with [`Error::Overflow`] if the filter reports more
+    /// output bytes than fit the remainder of the buffer.
+    #[must_use]
+    pub fn with_tx_filter(mut self, filter: fn(&[u8], &mut [u8]) -> usize) -> Self {
+        self.tx_filter = Some(filter);
+        self
+    }
+
+    /// Returns whether a command has been sent, whose response has not yet
+    /// been received, eg. because sending it was cancelled while awaiting the
+    /// response.
+    pub fn is_awaiting_response(&self) -> bool {
+        self.awaiting_response
+    }
+
     /// Returns a mutable reference to the inner writer.
     pub fn inner(&mut self) -> &mut W {
         &mut self.writer
     }
+
+    /// Returns the last command sent by the client, truncated to
+    /// [`LAST_COMMAND_LEN`](crate::LAST_COMMAND_LEN) bytes.
+    #[cfg(feature = "last-command")]
+    pub fn last_command(&self) -> &[u8] {
+        &self.last_command
+    }
+
+    /// Transmit the configured keepalive command, if nothing has been sent
+    /// for longer than the keepalive interval. See [`Config::keepalive`].
+    pub async fn maybe_keepalive(&mut self) -> Result<(), Error> {
+        let Some(interval) = self.config.keepalive_interval else {
+            return Ok(());
+        };
+
+        if self.last_sent.elapsed() <= interval {
+            return Ok(());
+        }
+
+        let cmd = self.config.keepalive_cmd;
+        let termination = self.config.default_termination.as_bytes();
+        let len = cmd.len() + termination.len();
+        let buf = self.buf.get_mut(..len).ok_or(Error::Overflow)?;
+        buf[..cmd.len()].copy_from_slice(cmd);
+        buf[cmd.len()..].copy_from_slice(termination);
+        self.res_slot.set_echo_expected(self.config.echo_enabled);
+        self.send_request(len).await?;
+
+        self.awaiting_response = true;
+        let result = match self.wait_response(self.config.keepalive_timeout).await {
+            Ok(response) => {
+                let response: Result<&[u8], InternalError> = (&*response).into();
+                response.map(|_| ()).map_err(Error::from)
+            }
+            Err(e) => Err(e),
+        };
+        self.awaiting_response = false;
+        result
+    }
+
+    /// Send a command, and collect the URCs received ahead of its response,
+    /// eg. a status URC triggered by the command.
+    ///
+    /// URCs already pending in `subscription` are discarded ahead of sending
+    /// the command, so that only URCs received after it are collected. URCs
+    /// exceeding the capacity `N` are left in the subscription.
+    pub async fn send_collecting_urcs<
+        Cmd,
+        Urc,
+        const N: usize,
+        const CAP: usize,
+        const SUBS: usize,
+    >(
+        &mut self,
+        cmd: &Cmd,
+        subscription: &mut UrcSubscription<'_, Urc, CAP, SUBS>,
+    ) -> Result<(Cmd::Response, heapless::Vec<Urc, N>), Error>
+    where
+        Cmd: AtatCmd,
+        Urc: AtatUrc,
+    {
+        while subscription.try_next_message_pure().is_some() {}
+
+        let response = self.send(cmd).await?;
+
+        let mut urcs = heapless::Vec::new();
+        while !urcs.is_full() {
+            match subscription.try_next_message_pure() {
+                Some(urc) => {
+                    let _ = urcs.push(urc);
+                }
+                None => break,
+            }
+        }
+
+        Ok((response, urcs))
+    }
+
+    /// Send a command, and return the raw response bytes along with the
+    /// parsed response, eg. for logging.
+    ///
+    /// The raw bytes are those handed to [`AtatCmd::parse`], truncated to `N`
+    /// bytes. For commands not expecting a response code, these are empty.
+    #[cfg(feature = "raw-response")]
+    pub async fn send_with_raw<Cmd: AtatCmd, const N: usize>(
+        &mut self,
+        cmd: &Cmd,
+    ) -> Result<(Cmd::Response, heapless::Vec<u8, N>), Error> {
+        let mut raw = heapless::Vec::new();
+        let response = self
+            .send_inspect(cmd, Cmd::MAX_TIMEOUT_MS, |bytes| {
+                let n = bytes.len().min(N);
+                let _ = raw.extend_from_slice(&bytes[..n]);
+            })
+            .await?;
+        Ok((response, raw))
+    }
 }
 
 impl<W: Write, const INGRESS_BUF_SIZE: usize> ErrorType for Client<'_, W, INGRESS_BUF_SIZE> {
@@ -47,6 +186,59 @@ impl<W: Write, const INGRESS_BUF_SIZE: usize> ErrorType for Client<'_, W, INGRES
 }
 
 impl<'a, W: Write, const INGRESS_BUF_SIZE: usize> Client<'a, W, INGRESS_BUF_SIZE> {
+    /// Send a command, waiting at most `timeout_ms` for the response and
+    /// handing its bytes to `inspect` before parsing them, if successful.
+    async fn send_inspect<Cmd: AtatCmd>(
+        &mut self,
+        cmd: &Cmd,
+        timeout_ms: u32,
+        inspect: impl FnOnce(&[u8]),
+    ) -> Result<Cmd::Response, Error> {
+        const {
+            assert!(
+                match Cmd::EXPECTED_RESPONSE_LEN {
+                    Some(len) => len <= INGRESS_BUF_SIZE,
+                    None => true,
+                },
+                "Expected response length exceeds ingress buffer size"
+            );
+        }
+
+        let len = cmd.write(self.buf);
+        let len = self
+            .config
+            .apply_default_termination::<Cmd>(self.buf, len)?;
+        let len = self
+            .config
+            .apply_sequence(self.buf, len, &mut self.sequence)?;
+        self.res_slot
+            .set_echo_expected(self.config.echo_enabled && Cmd::EXPECTS_ECHO);
+        self.send_request(len).await?;
+        if Cmd::NO_COOLDOWN {
+            self.cooldown_timer = None;
+        }
+        if !Cmd::EXPECTS_RESPONSE_CODE {
+            cmd.parse(Ok(&[]))
+        } else {
+            self.awaiting_response = true;
+            let result = match self
+                .wait_response(Duration::from_millis(timeout_ms.into()))
+                .await
+            {
+                Ok(response) => {
+                    let response: Result<&[u8], InternalError> = (&*response).into();
+                    if let Ok(bytes) = response {
+                        inspect(bytes);
+                    }
+                    cmd.parse(response)
+                }
+                Err(e) => Err(e),
+            };
+            self.awaiting_response = false;
+            result
+        }
+    }
+
     async fn send_request(&mut self, len: usize) -> Result<(), Error> {
         if len < 50 {
             debug!("Sending command: {:?}", LossyStr(&self.buf[..len]));
@@ -56,23 +248,59 @@ impl<'a, W: Write, const INGRESS_BUF_SIZE: usize> Client<'a, W, INGRESS_BUF_SIZE
 
         self.wait_cooldown_timer().await;
 
-        // Clear any pending response signal
+        // Apply the transmit filter, if any, into the remainder of the buffer
+        let tx = match self.tx_filter {
+            Some(filter) => {
+                let (cmd, out) = self.buf.split_at_mut(len);
+                let n = filter(cmd, out);
+                if n > out.len() {
+                    return Err(Error::Overflow);
+                }
+                len..len + n
+            }
+            None => 0..len,
+        };
+
+        #[cfg(feature = "last-command")]
+        {
+            self.last_command.clear();
+            let n = tx.len().min(crate::LAST_COMMAND_LEN);
+            let _ = self
+                .last_command
+                .extend_from_slice(&self.buf[tx.start..tx.start + n]);
+        }
+
+        // Wake the modem, if configured
+        if let Some(wakeup) = self.config.wakeup_bytes {
+            with_timeout(self.config.tx_timeout, self.writer.write_all(wakeup))
+                .await
+                .map_err(|_| Error::Timeout)?
+                .map_err(|_| Error::Write)?;
+
+            with_timeout(self.config.flush_timeout, self.writer.flush())
+                .await
+                .map_err(|_| Error::Timeout)?
+                .map_err(|_| Error::Write)?;
+
+            Timer::after(self.config.wakeup_delay).await;
+        }
+
+        // Clear any pending response signal, including the modem's response to
+        // the wakeup bytes
         self.res_slot.reset();
 
         // Write request
-        with_timeout(
-            self.config.tx_timeout,
-            self.writer.write_all(&self.buf[..len]),
-        )
-        .await
-        .map_err(|_| Error::Timeout)?
-        .map_err(|_| Error::Write)?;
+        with_timeout(self.config.tx_timeout, self.writer.write_all(&self.buf[tx]))
+            .await
+            .map_err(|_| Error::Timeout)?
+            .map_err(|_| Error::Write)?;
 
         with_timeout(self.config.flush_timeout, self.writer.flush())
             .await
             .map_err(|_| Error::Timeout)?
             .map_err(|_| Error::Write)?;
 
+        self.last_sent = Instant::now();
         self.start_cooldown_timer();
         Ok(())
     }
@@ -124,16 +352,15 @@ impl<'a, W: Write, const INGRESS_BUF_SIZE: usize> Client<'a, W, INGRESS_BUF_SIZE
 
 impl<W: Write, const INGRESS_BUF_SIZE: usize> AtatClient for Client<'_, W, INGRESS_BUF_SIZE> {
     async fn send<Cmd: AtatCmd>(&mut self, cmd: &Cmd) -> Result<Cmd::Response, Error> {
-        let len = cmd.write(self.buf);
-        self.send_request(len).await?;
-        if !Cmd::EXPECTS_RESPONSE_CODE {
-            cmd.parse(Ok(&[]))
-        } else {
-            let response = self
-                .wait_response(Duration::from_millis(Cmd::MAX_TIMEOUT_MS.into()))
-                .await?;
-            cmd.parse((&*response).into())
-        }
+        self.send_inspect(cmd, Cmd::MAX_TIMEOUT_MS, |_| {}).await
+    }
+
+    async fn send_with_timeout<Cmd: AtatCmd>(
+        &mut self,
+        cmd: &Cmd,
+        timeout_ms: u32,
+    ) -> Result<Cmd::Response, Error> {
+        self.send_inspect(cmd, timeout_ms, |_| {}).await
     }
 }
 
@@ -141,8 +368,8 @@ impl<W: Write, const INGRESS_BUF_SIZE: usize> AtatClient for Client<'_, W, INGRE
 mod tests {
     use super::*;
     use crate as atat;
-    use crate::atat_derive::{AtatCmd, AtatEnum, AtatResp};
-    use crate::Error;
+    use crate::atat_derive::{AtatCmd, AtatEnum, AtatResp, AtatUrc};
+    use crate::{AtDigester, AtatIngress, Error, Ingress, UrcChannel};
     use core::sync::atomic::{AtomicU64, Ordering};
     use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
     use embassy_sync::pubsub::PubSubChannel;
@@ -161,6 +388,14 @@ mod tests {
         pub rst: Option<ResetMode>,
     }
 
+    #[derive(Clone, AtatCmd)]
+    #[at_cmd("+COPS", NoResponse, abortable = true)]
+    pub struct AbortableCmd;
+
+    #[derive(Clone, AtatCmd)]
+    #[at_cmd("+CMEE=1", NoResponse, value_sep = false, no_cooldown = true)]
+    pub struct NoCooldownCmd;
+
     #[derive(Clone, PartialEq, AtatEnum)]
     #[at_enum(u8)]
     #[allow(clippy::upper_case_acronyms)]
@@ -188,6 +423,15 @@ mod tests {
     #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     pub struct NoResponse;
 
+    #[derive(AtatUrc, Clone, PartialEq, Debug)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    pub enum Urc {
+        #[at_urc(b"CONNECT OK")]
+        ConnectOk,
+        #[at_urc(b"CONNECT FAIL")]
+        ConnectFail,
+    }
+
     macro_rules! setup {
         ($config:expr) => {{
             static TX_CHANNEL: PubSubChannel<CriticalSectionRawMutex, String<64>, 1, 1, 1> =
@@ -206,6 +450,292 @@ mod tests {
         }};
     }
 
+    #[tokio::test]
+    async fn run_digests_read_bytes() {
+        struct Reader(&'static [u8]);
+        impl embedded_io::ErrorType for Reader {
+            type Error = embedded_io::ErrorKind;
+        }
+        impl embedded_io_async::Read for Reader {
+            async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                if self.0.is_empty() {
+                    // Wait for more data, which never arrives
+                    core::future::pending::<()>().await;
+                }
+                let len = buf.len().min(self.0.len());
+                buf[..len].copy_from_slice(&self.0[..len]);
+                self.0 = &self.0[len..];
+                Ok(len)
+            }
+        }
+
+        let slot = ResponseSlot::<TEST_RX_BUF_LEN>::new();
+        let urc_channel = UrcChannel::<Urc, 4, 1>::new();
+        let mut buf = [0; 100];
+        let mut ingress: Ingress<_, Urc, TEST_RX_BUF_LEN, 4, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &slot, &urc_channel);
+
+        let reader = Reader(b"\r\n+CFUN: 1\r\n\r\nOK\r\n");
+
+        tokio::select! {
+            _ = crate::asynch::run(&mut ingress, reader) => {}
+            response = slot.get() => {
+                assert_eq!(crate::Response::ok(b"+CFUN: 1"), *response);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn echo_disabled_before_first_send() {
+        let (_client, _tx, slot) = setup!(Config::new().echo_enabled(false));
+
+        let urc_channel = UrcChannel::<Urc, 4, 1>::new();
+        let mut buf = [0; 100];
+        let mut ingress: Ingress<_, Urc, TEST_RX_BUF_LEN, 4, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, slot, &urc_channel);
+
+        // The leading line is not stripped as echo, without sending anything
+        ingress.write(b"+CFUN: 1\r\n\r\nOK\r\n").await;
+        assert_eq!(crate::Response::ok(b"+CFUN: 1"), *slot.try_get().unwrap());
+    }
+
+    #[tokio::test]
+    async fn aborted_by_abort_token() {
+        let (mut client, mut tx, slot) = setup!(Config::new());
+
+        let urc_channel = UrcChannel::<Urc, 4, 1>::new();
+        let mut buf = [0; 100];
+        let digester = AtDigester::<Urc>::new().with_abort_token(b"ABORTED");
+        let mut ingress: Ingress<_, Urc, TEST_RX_BUF_LEN, 4, 1> =
+            Ingress::new(digester, &mut buf, slot, &urc_channel);
+
+        let modem = async {
+            tx.next_message_pure().await;
+            ingress.write(b"\r\nABORTED\r\n").await;
+        };
+
+        let (_, res) = join!(modem, client.send(&AbortableCmd));
+        assert_eq!(Err(Error::Aborted), res);
+    }
+
+    #[tokio::test]
+    async fn reboot_fails_pending_command() {
+        let (mut client, mut tx, slot) = setup!(Config::new());
+
+        let urc_channel = UrcChannel::<Urc, 4, 1>::new();
+        let mut buf = [0; 100];
+        let digester = AtDigester::<Urc>::new()
+            .with_reboot_indicators(&[b"RDY"], |_| {})
+            .with_reboot_failing_pending();
+        let mut ingress: Ingress<_, Urc, TEST_RX_BUF_LEN, 4, 1> =
+            Ingress::new(digester, &mut buf, slot, &urc_channel);
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        // Fails as soon as the modem reboots, rather than after 180 s
+        let modem = async {
+            tx.next_message_pure().await;
+            ingress.write(b"\r\nRDY\r\n").await;
+        };
+
+        let (_, res) = join!(modem, client.send(&cmd));
+        assert_eq!(Err(Error::Rebooted), res);
+    }
+
+    #[tokio::test]
+    async fn send_collecting_urcs() {
+        let (mut client, mut tx, slot) = setup!(Config::new());
+
+        let urc_channel = UrcChannel::<Urc, 4, 1>::new();
+        let mut subscription = urc_channel.subscribe().unwrap();
+        let mut buf = [0; 100];
+        let mut ingress: Ingress<_, Urc, TEST_RX_BUF_LEN, 4, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, slot, &urc_channel);
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        // Received ahead of sending the command, so not collected
+        ingress.write(b"\r\nCONNECT FAIL\r\n").await;
+
+        let modem = async {
+            tx.next_message_pure().await;
+            ingress.write(b"\r\nCONNECT OK\r\n\r\nOK\r\n").await;
+        };
+
+        let (_, res): (_, Result<(NoResponse, heapless::Vec<Urc, 2>), Error>) =
+            join!(modem, client.send_collecting_urcs(&cmd, &mut subscription));
+        let (response, urcs) = res.unwrap();
+        assert_eq!(NoResponse, response);
+        assert_eq!(&[Urc::ConnectOk], urcs.as_slice());
+        assert!(subscription.try_next_message_pure().is_none());
+    }
+
+    #[tokio::test]
+    async fn awaiting_response() {
+        let (mut client, mut tx, slot) = setup!(Config::new());
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        assert!(!client.is_awaiting_response());
+
+        // Cancel the command before a response is received
+        let res = with_timeout(Duration::from_millis(10), client.send(&cmd)).await;
+        assert!(res.is_err());
+        assert!(client.is_awaiting_response());
+        tx.next_message_pure().await;
+
+        let modem = async {
+            tx.next_message_pure().await;
+            slot.signal_response(Ok(&[])).unwrap();
+        };
+
+        let (_, res) = join!(modem, client.send(&cmd));
+        assert_eq!(Ok(NoResponse), res);
+        assert!(!client.is_awaiting_response());
+    }
+
+    #[tokio::test]
+    async fn no_cooldown_after_command() {
+        let (mut client, mut tx, slot) =
+            setup!(Config::new().cmd_cooldown(Duration::from_millis(500)));
+
+        let modem = async {
+            for _ in 0..2 {
+                tx.next_message_pure().await;
+                slot.signal_response(Ok(&[])).unwrap();
+            }
+        };
+
+        let start = Instant::now();
+        let client = async {
+            assert_eq!(Ok(NoResponse), client.send(&NoCooldownCmd).await);
+            assert_eq!(Ok(NoResponse), client.send(&NoCooldownCmd).await);
+        };
+        join!(modem, client);
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn urcs_received_during_send() {
+        let (mut client, mut tx, slot) = setup!(Config::new());
+
+        let urc_channel = UrcChannel::<Urc, 4, 1>::new();
+        let mut sub = urc_channel.subscribe().unwrap();
+        let mut buf = [0; 100];
+        let mut ingress: Ingress<_, Urc, TEST_RX_BUF_LEN, 4, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, slot, &urc_channel);
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        let modem = async {
+            tx.next_message_pure().await;
+            ingress.write(b"\r\nCONNECT OK\r\n").await;
+            ingress.write(b"\r\nCONNECT FAIL\r\n").await;
+            ingress.write(b"\r\nOK\r\n").await;
+        };
+
+        let (_, res) = join!(modem, client.send(&cmd));
+        assert_eq!(Ok(NoResponse), res);
+
+        assert_eq!(Some(Urc::ConnectOk), sub.try_next_message_pure());
+        assert_eq!(Some(Urc::ConnectFail), sub.try_next_message_pure());
+        assert_eq!(None, sub.try_next_message_pure());
+    }
+
+    #[tokio::test]
+    async fn keepalive_after_interval() {
+        let (mut client, mut tx, slot) = setup!(Config::new().keepalive(Duration::from_millis(50)));
+
+        // Interval has not yet elapsed
+        assert_eq!(Ok(()), client.maybe_keepalive().await);
+        assert_eq!(None, tx.try_next_message_pure());
+
+        Timer::after(Duration::from_millis(60)).await;
+
+        let sent = async {
+            assert_eq!("AT\r\n", tx.next_message_pure().await.as_str());
+            slot.signal_response(Ok(&[])).unwrap();
+        };
+
+        let (_, res) = join!(sent, client.maybe_keepalive());
+        assert_eq!(Ok(()), res);
+
+        // Interval restarted by the keepalive
+        assert_eq!(Ok(()), client.maybe_keepalive().await);
+        assert_eq!(None, tx.try_next_message_pure());
+    }
+
+    #[tokio::test]
+    async fn keepalive_timeout() {
+        let (mut client, mut tx, _slot) = setup!(Config::new()
+            .cmd_cooldown(Duration::from_millis(0))
+            .keepalive(Duration::from_millis(1))
+            .keepalive_timeout(Duration::from_millis(10)));
+
+        Timer::after(Duration::from_millis(5)).await;
+        assert_eq!(Err(Error::Timeout), client.maybe_keepalive().await);
+        assert_eq!(Some("AT\r\n"), tx.try_next_message_pure().as_deref());
+        assert!(!client.is_awaiting_response());
+
+        // Cancelled while awaiting the response
+        Timer::after(Duration::from_millis(5)).await;
+        let res = with_timeout(Duration::from_millis(5), client.maybe_keepalive()).await;
+        assert!(res.is_err());
+        assert!(client.is_awaiting_response());
+    }
+
+    #[tokio::test]
+    async fn keepalive_cmd_overflow() {
+        static CMD: [u8; 1001] = [b'A'; 1001];
+        let (mut client, _tx, _slot) = setup!(Config::new()
+            .keepalive(Duration::from_millis(1))
+            .keepalive_cmd(&CMD));
+
+        Timer::after(Duration::from_millis(5)).await;
+        assert_eq!(Err(Error::Overflow), client.maybe_keepalive().await);
+    }
+
+    #[tokio::test]
+    async fn wakeup_bytes_precede_command() {
+        let (mut client, mut tx, slot) = setup!(Config::new()
+            .wakeup_bytes(b"AT\r")
+            .wakeup_delay(Duration::from_millis(10)));
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        let sent = tokio::spawn(async move {
+            assert_eq!("AT\r", tx.next_message_pure().await.as_str());
+            // The modem answers the wakeup bytes with OK
+            slot.signal_response(Ok(&[])).unwrap();
+            assert_eq!("AT+CFUN=4,0\r\n", tx.next_message_pure().await.as_str());
+            slot.signal_response(Err(InternalError::Error)).unwrap();
+        });
+
+        let send = tokio::spawn(async move {
+            assert_eq!(Err(Error::Error), client.send(&cmd).await);
+        });
+
+        let (sent, send) = join!(sent, send);
+        sent.unwrap();
+        send.unwrap();
+    }
+
     #[tokio::test]
     async fn custom_timeout() {
         static CALL_COUNT: AtomicU64 = AtomicU64::new(0);
@@ -4,7 +4,23 @@ mod simple_client;
 pub use client::Client;
 pub use simple_client::SimpleClient;
 
-use crate::{AtatCmd, Error};
+use crate::{AtatCmd, AtatIngress, Error};
+
+/// Ingress task, reading bytes from `reader` as they arrive and digesting them
+/// into the response slot and URC channel of `ingress`.
+///
+/// This never returns, and is meant to be run as a dedicated task alongside
+/// the [`Client`], eg.
+///
+/// ```ignore
+/// #[embassy_executor::task]
+/// async fn ingress_task(mut ingress: Ingress<'static, ...>, reader: UartRx<'static, ...>) -> ! {
+///     atat::asynch::run(&mut ingress, reader).await
+/// }
+/// ```
+pub async fn run<I: AtatIngress, R: embedded_io_async::Read>(ingress: &mut I, reader: R) -> ! {
+    ingress.read_from(reader).await
+}
 
 pub trait AtatClient {
     /// Send an AT command.
@@ -16,23 +32,48 @@ pub trait AtatClient {
     /// the slave AT device time to deliver URC's.
     async fn send<Cmd: AtatCmd>(&mut self, cmd: &Cmd) -> Result<Cmd::Response, Error>;
 
+    /// Send an AT command, waiting at most `timeout_ms` for the response
+    /// rather than [`AtatCmd::MAX_TIMEOUT_MS`].
+    ///
+    /// Used by `send_retry` to scale the timeout of reattempts. The default
+    /// implementation ignores `timeout_ms` and falls back to `send`.
+    async fn send_with_timeout<Cmd: AtatCmd>(
+        &mut self,
+        cmd: &Cmd,
+        timeout_ms: u32,
+    ) -> Result<Cmd::Response, Error> {
+        let _ = timeout_ms;
+        self.send(cmd).await
+    }
+
     async fn send_retry<Cmd: AtatCmd>(&mut self, cmd: &Cmd) -> Result<Cmd::Response, Error> {
+        let mut error = Error::Timeout;
+        let mut timeout_ms = Cmd::MAX_TIMEOUT_MS;
         for attempt in 1..=Cmd::ATTEMPTS {
             if attempt > 1 {
                 debug!("Attempt {}:", attempt);
+                timeout_ms = timeout_ms.saturating_mul(Cmd::RETRY_TIMEOUT_MULTIPLIER);
             }
 
-            match self.send(cmd).await {
-                Err(Error::Timeout) => {}
+            match self.send_with_timeout(cmd, timeout_ms).await {
                 Err(Error::Parse) => {
                     if !Cmd::REATTEMPT_ON_PARSE_ERR {
                         return Err(Error::Parse);
                     }
                 }
+                Err(Error::CmeError(e))
+                    if attempt < Cmd::ATTEMPTS && Cmd::RETRIABLE_CME_ERRORS.contains(&e) =>
+                {
+                    debug!("Retrying on {:?}", e);
+                }
+                Err(e) if Cmd::is_retriable_error(&e) => {
+                    debug!("Retrying on {:?}", e);
+                    error = e;
+                }
                 r => return r,
             }
         }
-        Err(Error::Timeout)
+        Err(error)
     }
 }
 
@@ -43,4 +84,12 @@ where
     async fn send<Cmd: AtatCmd>(&mut self, cmd: &Cmd) -> Result<Cmd::Response, Error> {
         T::send(self, cmd).await
     }
+
+    async fn send_with_timeout<Cmd: AtatCmd>(
+        &mut self,
+        cmd: &Cmd,
+        timeout_ms: u32,
+    ) -> Result<Cmd::Response, Error> {
+        T::send_with_timeout(self, cmd, timeout_ms).await
+    }
 }
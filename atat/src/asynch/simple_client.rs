@@ -10,6 +10,7 @@ pub struct SimpleClient<'a, RW: Read + Write, D: Digester> {
     pos: usize,
     config: Config,
     cooldown_timer: Option<Timer>,
+    sequence: u8,
 }
 
 impl<'a, RW: Read + Write, D: Digester> SimpleClient<'a, RW, D> {
@@ -21,6 +22,7 @@ impl<'a, RW: Read + Write, D: Digester> SimpleClient<'a, RW, D> {
             config,
             pos: 0,
             cooldown_timer: None,
+            sequence: 0,
         }
     }
 
@@ -123,7 +125,29 @@ impl<'a, RW: Read + Write, D: Digester> SimpleClient<'a, RW, D> {
 
 impl<RW: Read + Write, D: Digester> AtatClient for SimpleClient<'_, RW, D> {
     async fn send<Cmd: AtatCmd>(&mut self, cmd: &Cmd) -> Result<Cmd::Response, Error> {
+        self.send_with_timeout(cmd, Cmd::MAX_TIMEOUT_MS).await
+    }
+
+    async fn send_with_timeout<Cmd: AtatCmd>(
+        &mut self,
+        cmd: &Cmd,
+        timeout_ms: u32,
+    ) -> Result<Cmd::Response, Error> {
+        // The response buffer is only known at runtime here, so the check
+        // done at compile time by the other clients is done before sending.
+        if Cmd::EXPECTED_RESPONSE_LEN.is_some_and(|len| len > self.buf.len()) {
+            return Err(Error::Capacity);
+        }
+
         let len = cmd.write(self.buf);
+        let len = self
+            .config
+            .apply_default_termination::<Cmd>(self.buf, len)?;
+        let len = self
+            .config
+            .apply_sequence(self.buf, len, &mut self.sequence)?;
+        self.digester
+            .set_echo_stripping(self.config.echo_enabled && Cmd::EXPECTS_ECHO);
 
         self.send_request(len).await?;
         if !Cmd::EXPECTS_RESPONSE_CODE {
@@ -132,7 +156,7 @@ impl<RW: Read + Write, D: Digester> AtatClient for SimpleClient<'_, RW, D> {
 
         self.pos = 0;
 
-        let timeout = Duration::from_millis(Cmd::MAX_TIMEOUT_MS.into());
+        let timeout = Duration::from_millis(timeout_ms.into());
         embassy_time::with_timeout(timeout, async {
             loop {
                 self.read_response_chunk().await?;
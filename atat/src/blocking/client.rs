@@ -5,7 +5,7 @@ use super::AtatClient;
 use crate::{
     helpers::LossyStr,
     response_slot::{ResponseSlot, ResponseSlotGuard},
-    AtatCmd, Config, Error,
+    AtatCmd, AtatUrc, Config, Error, InternalError, UrcSubscription,
 };
 
 /// Client responsible for handling send, receive and timeout from the
@@ -21,7 +21,12 @@ where
     res_slot: &'a ResponseSlot<INGRESS_BUF_SIZE>,
     buf: &'a mut [u8],
     cooldown_timer: Option<Instant>,
+    sequence: u8,
     config: Config,
+    last_sent: Instant,
+    tx_filter: Option<fn(&[u8], &mut [u8]) -> usize>,
+    #[cfg(feature = "last-command")]
+    last_command: heapless::Vec<u8, { crate::LAST_COMMAND_LEN }>,
 }
 
 impl<'a, W, const INGRESS_BUF_SIZE: usize> Client<'a, W, INGRESS_BUF_SIZE>
@@ -34,20 +39,169 @@ where
         buf: &'a mut [u8],
         config: Config,
     ) -> Self {
+        res_slot.set_echo_enabled(config.echo_enabled);
         Self {
             writer,
             res_slot,
             buf,
             cooldown_timer: None,
+            sequence: 0,
             config,
+            last_sent: Instant::now(),
+            tx_filter: None,
+            #[cfg(feature = "last-command")]
+            last_command: heapless::Vec::new(),
         }
     }
 
+    /// Set a filter applied to every serialized command, right before it is
+    /// written to the transmit side.
+    ///
+    /// The filter is handed the serialized command, and the unused remainder
+    /// of the client buffer to write its output into. It returns the number of
+    /// output bytes, which are then transmitted instead of the command. Both
+    /// slices are owned by the client and only valid for the duration of the
+    /// call.
+    ///
+    /// Sending fails with [`Error::Overflow`] if the filter reports more
+    /// output bytes than fit the remainder of the buffer.
+    #[must_use]
+    pub fn with_tx_filter(mut self, filter: fn(&[u8], &mut [u8]) -> usize) -> Self {
+        self.tx_filter = Some(filter);
+        self
+    }
+
     /// Returns a mutable reference to the inner writer.
     pub fn inner(&mut self) -> &mut W {
         &mut self.writer
     }
 
+    /// Returns the last command sent by the client, truncated to
+    /// [`LAST_COMMAND_LEN`](crate::LAST_COMMAND_LEN) bytes.
+    #[cfg(feature = "last-command")]
+    pub fn last_command(&self) -> &[u8] {
+        &self.last_command
+    }
+
+    /// Transmit the configured keepalive command, if nothing has been sent
+    /// for longer than the keepalive interval. See [`Config::keepalive`].
+    pub fn maybe_keepalive(&mut self) -> Result<(), Error> {
+        let Some(interval) = self.config.keepalive_interval else {
+            return Ok(());
+        };
+
+        if self.last_sent.elapsed() <= interval {
+            return Ok(());
+        }
+
+        let cmd = self.config.keepalive_cmd;
+        let termination = self.config.default_termination.as_bytes();
+        let len = cmd.len() + termination.len();
+        let buf = self.buf.get_mut(..len).ok_or(Error::Overflow)?;
+        buf[..cmd.len()].copy_from_slice(cmd);
+        buf[cmd.len()..].copy_from_slice(termination);
+        self.res_slot.set_echo_expected(self.config.echo_enabled);
+        self.send_request(len)?;
+
+        let response = self.wait_response(self.config.keepalive_timeout)?;
+        let response: Result<&[u8], InternalError> = (&*response).into();
+        response.map(|_| ()).map_err(Error::from)
+    }
+
+    /// Send a command, and collect the URCs received ahead of its response,
+    /// eg. a status URC triggered by the command.
+    ///
+    /// URCs already pending in `subscription` are discarded ahead of sending
+    /// the command, so that only URCs received after it are collected. URCs
+    /// exceeding the capacity `N` are left in the subscription.
+    pub fn send_collecting_urcs<Cmd, Urc, const N: usize, const CAP: usize, const SUBS: usize>(
+        &mut self,
+        cmd: &Cmd,
+        subscription: &mut UrcSubscription<'_, Urc, CAP, SUBS>,
+    ) -> Result<(Cmd::Response, heapless::Vec<Urc, N>), Error>
+    where
+        Cmd: AtatCmd,
+        Urc: AtatUrc,
+    {
+        while subscription.try_next_message_pure().is_some() {}
+
+        let response = self.send(cmd)?;
+
+        let mut urcs = heapless::Vec::new();
+        while !urcs.is_full() {
+            match subscription.try_next_message_pure() {
+                Some(urc) => {
+                    let _ = urcs.push(urc);
+                }
+                None => break,
+            }
+        }
+
+        Ok((response, urcs))
+    }
+
+    /// Send a command, and return the raw response bytes along with the
+    /// parsed response, eg. for logging.
+    ///
+    /// The raw bytes are those handed to [`AtatCmd::parse`], truncated to `N`
+    /// bytes. For commands not expecting a response code, these are empty.
+    #[cfg(feature = "raw-response")]
+    pub fn send_with_raw<Cmd: AtatCmd, const N: usize>(
+        &mut self,
+        cmd: &Cmd,
+    ) -> Result<(Cmd::Response, heapless::Vec<u8, N>), Error> {
+        let mut raw = heapless::Vec::new();
+        let response = self.send_inspect(cmd, Cmd::MAX_TIMEOUT_MS, |bytes| {
+            let n = bytes.len().min(N);
+            let _ = raw.extend_from_slice(&bytes[..n]);
+        })?;
+        Ok((response, raw))
+    }
+
+    /// Send a command, waiting at most `timeout_ms` for the response and
+    /// handing its bytes to `inspect` before parsing them, if successful.
+    fn send_inspect<Cmd: AtatCmd>(
+        &mut self,
+        cmd: &Cmd,
+        timeout_ms: u32,
+        inspect: impl FnOnce(&[u8]),
+    ) -> Result<Cmd::Response, Error> {
+        const {
+            assert!(
+                match Cmd::EXPECTED_RESPONSE_LEN {
+                    Some(len) => len <= INGRESS_BUF_SIZE,
+                    None => true,
+                },
+                "Expected response length exceeds ingress buffer size"
+            );
+        }
+
+        let len = cmd.write(self.buf);
+        let len = self
+            .config
+            .apply_default_termination::<Cmd>(self.buf, len)?;
+        let len = self
+            .config
+            .apply_sequence(self.buf, len, &mut self.sequence)?;
+        self.res_slot
+            .set_echo_expected(self.config.echo_enabled && Cmd::EXPECTS_ECHO);
+        self.send_request(len)?;
+        if Cmd::NO_COOLDOWN {
+            self.cooldown_timer = None;
+        }
+        if !Cmd::EXPECTS_RESPONSE_CODE {
+            cmd.parse(Ok(&[]))
+        } else {
+            let timeout = Duration::from_millis(timeout_ms.into());
+            let response = self.wait_response(timeout)?;
+            let response: Result<&[u8], InternalError> = (&*response).into();
+            if let Ok(bytes) = response {
+                inspect(bytes);
+            }
+            cmd.parse(response)
+        }
+    }
+
     fn send_request(&mut self, len: usize) -> Result<(), Error> {
         if len < 50 {
             debug!("Sending command: {:?}", LossyStr(&self.buf[..len]));
@@ -57,15 +211,50 @@ where
 
         self.wait_cooldown_timer();
 
-        // Clear any pending response signal
+        // Apply the transmit filter, if any, into the remainder of the buffer
+        let tx = match self.tx_filter {
+            Some(filter) => {
+                let (cmd, out) = self.buf.split_at_mut(len);
+                let n = filter(cmd, out);
+                if n > out.len() {
+                    return Err(Error::Overflow);
+                }
+                len..len + n
+            }
+            None => 0..len,
+        };
+
+        #[cfg(feature = "last-command")]
+        {
+            self.last_command.clear();
+            let n = tx.len().min(crate::LAST_COMMAND_LEN);
+            let _ = self
+                .last_command
+                .extend_from_slice(&self.buf[tx.start..tx.start + n]);
+        }
+
+        // Wake the modem, if configured
+        if let Some(wakeup) = self.config.wakeup_bytes {
+            self.writer.write_all(wakeup).map_err(|_| Error::Write)?;
+            self.writer.flush().map_err(|_| Error::Write)?;
+
+            let wakeup_done = Instant::now() + self.config.wakeup_delay;
+            while Instant::now() < wakeup_done {
+                core::hint::spin_loop();
+            }
+        }
+
+        // Clear any pending response signal, including the modem's response to
+        // the wakeup bytes
         self.res_slot.reset();
 
         // Write request
         self.writer
-            .write_all(&self.buf[..len])
+            .write_all(&self.buf[tx])
             .map_err(|_| Error::Write)?;
         self.writer.flush().map_err(|_| Error::Write)?;
 
+        self.last_sent = Instant::now();
         self.start_cooldown_timer();
         Ok(())
     }
@@ -113,14 +302,15 @@ where
     W: Write,
 {
     fn send<Cmd: AtatCmd>(&mut self, cmd: &Cmd) -> Result<Cmd::Response, Error> {
-        let len = cmd.write(self.buf);
-        self.send_request(len)?;
-        if !Cmd::EXPECTS_RESPONSE_CODE {
-            cmd.parse(Ok(&[]))
-        } else {
-            let response = self.wait_response(Duration::from_millis(Cmd::MAX_TIMEOUT_MS.into()))?;
-            cmd.parse((&*response).into())
-        }
+        self.send_inspect(cmd, Cmd::MAX_TIMEOUT_MS, |_| {})
+    }
+
+    fn send_with_timeout<Cmd: AtatCmd>(
+        &mut self,
+        cmd: &Cmd,
+        timeout_ms: u32,
+    ) -> Result<Cmd::Response, Error> {
+        self.send_inspect(cmd, timeout_ms, |_| {})
     }
 }
 
@@ -128,7 +318,7 @@ where
 mod test {
     use super::*;
     use crate::atat_derive::{AtatCmd, AtatEnum, AtatResp, AtatUrc};
-    use crate::{self as atat, InternalError};
+    use crate::{self as atat, CmeError, InternalError};
     use core::sync::atomic::{AtomicU64, Ordering};
     use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
     use embassy_sync::pubsub::PubSubChannel;
@@ -167,6 +357,44 @@ mod test {
         pub rst: Option<ResetMode>,
     }
 
+    #[derive(Clone, AtatCmd)]
+    #[at_cmd(
+        "+CPIN",
+        NoResponse,
+        attempts = 3,
+        retriable_cme_errors = [atat::CmeError::SimBusy]
+    )]
+    pub struct RetryOnSimBusy;
+
+    fn retry_on_error(e: &Error) -> bool {
+        matches!(e, Error::Error)
+    }
+
+    #[derive(Clone, AtatCmd)]
+    #[at_cmd("+COPS", NoResponse, attempts = 2, retriable_error = retry_on_error)]
+    pub struct RetryOnError;
+
+    #[derive(Clone, AtatCmd)]
+    #[at_cmd(
+        "+COPS",
+        NoResponse,
+        timeout_ms = 100,
+        attempts = 2,
+        retry_timeout_multiplier = 3
+    )]
+    pub struct RetryWithLongerTimeout;
+
+    #[derive(Clone, AtatCmd)]
+    #[at_cmd("+CMEE=1", NoResponse, value_sep = false, termination = "\r\n")]
+    pub struct FixedTerminationCmd;
+
+    #[derive(Clone, AtatCmd)]
+    #[at_cmd("+cfun", NoResponse)]
+    pub struct LowercaseCmd {
+        #[at_arg(position = 0)]
+        pub fun: Functionality,
+    }
+
     #[derive(Clone, AtatCmd)]
     #[at_cmd("+FUN", NoResponse, timeout_ms = 180000)]
     pub struct Test2Cmd {
@@ -324,6 +552,345 @@ mod test {
         sent.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn retry_on_retriable_cme_error() {
+        let (mut client, mut tx, rx) = setup!(Config::new());
+
+        let sent = tokio::spawn(async move {
+            tx.next_message_pure().await;
+            rx.signal_response(Err(InternalError::CmeError(CmeError::SimBusy)))
+                .unwrap();
+
+            tx.next_message_pure().await;
+            rx.signal_response(Ok(&[])).unwrap();
+
+            tx.next_message_pure().await;
+            rx.signal_response(Err(InternalError::CmeError(CmeError::SimNotInserted)))
+                .unwrap();
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(client.send_retry(&RetryOnSimBusy), Ok(NoResponse));
+            assert_eq!(
+                client.send_retry(&RetryOnSimBusy),
+                Err(Error::CmeError(CmeError::SimNotInserted))
+            );
+        })
+        .await
+        .unwrap();
+
+        sent.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn retry_on_custom_retriable_error() {
+        let (mut client, mut tx, rx) = setup!(Config::new());
+
+        let sent = tokio::spawn(async move {
+            tx.next_message_pure().await;
+            rx.signal_response(Err(InternalError::Error)).unwrap();
+
+            tx.next_message_pure().await;
+            rx.signal_response(Ok(&[])).unwrap();
+
+            tx.next_message_pure().await;
+            rx.signal_response(Err(InternalError::Error)).unwrap();
+
+            tx.next_message_pure().await;
+            rx.signal_response(Err(InternalError::Error)).unwrap();
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(client.send_retry(&RetryOnError), Ok(NoResponse));
+            assert_eq!(client.send_retry(&RetryOnError), Err(Error::Error));
+        })
+        .await
+        .unwrap();
+
+        sent.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn retry_scales_timeout() {
+        static SHORTEST_MS: AtomicU64 = AtomicU64::new(u64::MAX);
+        static LONGEST_MS: AtomicU64 = AtomicU64::new(0);
+
+        fn custom_response_timeout(sent: Instant, timeout: Duration) -> Instant {
+            SHORTEST_MS.fetch_min(timeout.as_millis(), Ordering::Relaxed);
+            LONGEST_MS.fetch_max(timeout.as_millis(), Ordering::Relaxed);
+            // Time out quickly, regardless of the timeout of the attempt
+            sent + Duration::from_millis(50)
+        }
+
+        let (mut client, mut tx, _rx) =
+            setup!(Config::new().get_response_timeout(custom_response_timeout));
+
+        let sent = tokio::spawn(async move {
+            // Do not emit any response, effectively timing out both attempts
+            tx.next_message_pure().await;
+            tx.next_message_pure().await;
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(
+                Err(Error::Timeout),
+                client.send_retry(&RetryWithLongerTimeout)
+            );
+        })
+        .await
+        .unwrap();
+
+        sent.await.unwrap();
+
+        assert_eq!(100, SHORTEST_MS.load(Ordering::Relaxed));
+        assert_eq!(300, LONGEST_MS.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn keepalive_after_interval() {
+        let (mut client, mut tx, rx) = setup!(Config::new().keepalive(Duration::from_millis(50)));
+
+        // Interval has not yet elapsed
+        assert_eq!(Ok(()), client.maybe_keepalive());
+        assert_eq!(None, tx.try_next_message_pure());
+
+        let sent = tokio::spawn(async move {
+            assert_eq!("AT\r\n", tx.next_message_pure().await.as_str());
+            rx.signal_response(Ok(&[])).unwrap();
+            tx
+        });
+
+        let mut client = tokio::task::spawn_blocking(move || {
+            embassy_time::block_for(Duration::from_millis(60));
+            assert_eq!(Ok(()), client.maybe_keepalive());
+            client
+        })
+        .await
+        .unwrap();
+
+        let mut tx = sent.await.unwrap();
+
+        // Interval restarted by the keepalive
+        assert_eq!(Ok(()), client.maybe_keepalive());
+        assert_eq!(None, tx.try_next_message_pure());
+    }
+
+    #[tokio::test]
+    async fn echo_disabled_in_config() {
+        let (mut client, mut tx, rx) = setup!(Config::new().echo_enabled(false));
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        let sent = tokio::spawn(async move {
+            tx.next_message_pure().await;
+            assert!(!rx.echo_expected());
+            rx.signal_response(Ok(&[])).unwrap();
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(client.send(&cmd), Ok(NoResponse));
+        })
+        .await
+        .unwrap();
+
+        sent.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn wakeup_response_is_discarded() {
+        let (mut client, mut tx, rx) = setup!(Config::new()
+            .wakeup_bytes(b"AT\r")
+            .wakeup_delay(Duration::from_millis(50)));
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        let sent = tokio::spawn(async move {
+            assert_eq!("AT\r", tx.next_message_pure().await.as_str());
+            // The modem answers the wakeup bytes with OK
+            rx.signal_response(Ok(&[])).unwrap();
+            assert_eq!("AT+CFUN=4,0\r\n", tx.next_message_pure().await.as_str());
+            rx.signal_response(Err(InternalError::Error)).unwrap();
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(Err(Error::Error), client.send(&cmd));
+        })
+        .await
+        .unwrap();
+
+        sent.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn tx_filter() {
+        let (client, mut tx, rx) = setup!(Config::new());
+        let mut client = client.with_tx_filter(|cmd, out| {
+            for (o, c) in out.iter_mut().zip(cmd) {
+                *o = c.to_ascii_uppercase();
+            }
+            cmd.len().min(out.len())
+        });
+
+        let cmd = LowercaseCmd {
+            fun: Functionality::APM,
+        };
+
+        let sent = tokio::spawn(async move {
+            let sent = tx.next_message_pure().await;
+            rx.signal_response(Ok(&[])).unwrap();
+            sent
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(client.send(&cmd), Ok(NoResponse));
+        })
+        .await
+        .unwrap();
+
+        assert_eq!("AT+CFUN=4\r\n", &sent.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn tx_filter_overflow() {
+        let (client, mut tx, _rx) = setup!(Config::new());
+        let mut client = client.with_tx_filter(|_, out| out.len() + 1);
+
+        let cmd = LowercaseCmd {
+            fun: Functionality::APM,
+        };
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(client.send(&cmd), Err(Error::Overflow));
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(None, tx.try_next_message_pure());
+    }
+
+    #[tokio::test]
+    async fn default_termination() {
+        let (mut client, mut tx, rx) = setup!(Config::new().default_termination("\r"));
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        let sent = tokio::spawn(async move {
+            let default = tx.next_message_pure().await;
+            rx.signal_response(Ok(&[])).unwrap();
+            let fixed = tx.next_message_pure().await;
+            rx.signal_response(Ok(&[])).unwrap();
+            (default, fixed)
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(client.send(&cmd), Ok(NoResponse));
+            assert_eq!(client.send(&FixedTerminationCmd), Ok(NoResponse));
+        })
+        .await
+        .unwrap();
+
+        let (default, fixed) = sent.await.unwrap();
+        assert_eq!("AT+CFUN=4,0\r", &default);
+        assert_eq!("AT+CMEE=1\r\n", &fixed);
+    }
+
+    #[tokio::test]
+    async fn append_sequence() {
+        let (mut client, mut tx, rx) = setup!(Config::new().append_sequence(true));
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        let sent = tokio::spawn(async move {
+            let first = tx.next_message_pure().await;
+            rx.signal_response(Ok(&[])).unwrap();
+            let second = tx.next_message_pure().await;
+            rx.signal_response(Ok(&[])).unwrap();
+            (first, second)
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(client.send(&cmd), Ok(NoResponse));
+            assert_eq!(client.send(&cmd), Ok(NoResponse));
+        })
+        .await
+        .unwrap();
+
+        let (first, second) = sent.await.unwrap();
+        assert_eq!("AT+CFUN=4,0,0\r\n", &first);
+        assert_eq!("AT+CFUN=4,0,1\r\n", &second);
+    }
+
+    #[cfg(feature = "raw-response")]
+    #[tokio::test]
+    async fn send_with_raw() {
+        let (mut client, mut tx, rx) = setup!(Config::new());
+
+        let cmd = TestRespStringCmd {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+        let response = b"+CUN: 22,16,\"0123456789012345\"";
+
+        let sent = tokio::spawn(async move {
+            tx.next_message_pure().await;
+            rx.signal_response(Ok(response)).unwrap();
+        });
+
+        tokio::task::spawn_blocking(move || {
+            let (parsed, raw) = client.send_with_raw::<_, 64>(&cmd).unwrap();
+            assert_eq!(
+                TestResponseString {
+                    socket: 22,
+                    length: 16,
+                    data: String::<64>::try_from("0123456789012345").unwrap()
+                },
+                parsed
+            );
+            assert_eq!(&response[..], raw.as_slice());
+        })
+        .await
+        .unwrap();
+
+        sent.await.unwrap();
+    }
+
+    #[cfg(feature = "last-command")]
+    #[tokio::test]
+    async fn last_command() {
+        let (mut client, mut tx, rx) = setup!(Config::new());
+
+        let cmd = SetModuleFunctionality {
+            fun: Functionality::APM,
+            rst: Some(ResetMode::DontReset),
+        };
+
+        let sent = tokio::spawn(async move {
+            tx.next_message_pure().await;
+            rx.signal_response(Err(InternalError::Error)).unwrap();
+        });
+
+        tokio::task::spawn_blocking(move || {
+            assert_eq!(client.send(&cmd), Err(Error::Error));
+            assert_eq!(client.last_command(), b"AT+CFUN=4,0\r\n");
+        })
+        .await
+        .unwrap();
+
+        sent.await.unwrap();
+    }
+
     #[tokio::test]
     async fn string_sent() {
         let (mut client, mut tx, rx) = setup!(Config::new());
@@ -356,8 +923,8 @@ mod test {
         .unwrap();
 
         let (sent0, sent1) = sent.await.unwrap();
-        assert_eq!("AT+CFUN=4,0\r", &sent0);
-        assert_eq!("AT+FUN=1,6\r", &sent1);
+        assert_eq!("AT+CFUN=4,0\r\n", &sent0);
+        assert_eq!("AT+FUN=1,6\r\n", &sent1);
     }
 
     #[tokio::test]
@@ -382,7 +949,7 @@ mod test {
         .unwrap();
 
         let sent = sent.await.unwrap();
-        assert_eq!("AT+CFUN=4,0\r", &sent);
+        assert_eq!("AT+CFUN=4,0\r\n", &sent);
     }
 
     // Test response containing string
@@ -21,17 +21,42 @@ pub trait AtatClient {
     /// the slave AT device time to deliver URC's.
     fn send<A: AtatCmd>(&mut self, cmd: &A) -> Result<A::Response, Error>;
 
+    /// Send an AT command, waiting at most `timeout_ms` for the response
+    /// rather than [`AtatCmd::MAX_TIMEOUT_MS`].
+    ///
+    /// Used by `send_retry` to scale the timeout of reattempts. The default
+    /// implementation ignores `timeout_ms` and falls back to `send`.
+    fn send_with_timeout<A: AtatCmd>(
+        &mut self,
+        cmd: &A,
+        timeout_ms: u32,
+    ) -> Result<A::Response, Error> {
+        let _ = timeout_ms;
+        self.send(cmd)
+    }
+
     fn send_retry<A: AtatCmd>(&mut self, cmd: &A) -> Result<A::Response, Error> {
+        let mut error = Error::Timeout;
+        let mut timeout_ms = A::MAX_TIMEOUT_MS;
         for attempt in 1..=A::ATTEMPTS {
             if attempt > 1 {
                 debug!("Attempt {}:", attempt);
+                timeout_ms = timeout_ms.saturating_mul(A::RETRY_TIMEOUT_MULTIPLIER);
             }
 
-            match self.send(cmd) {
-                Err(Error::Timeout) => {}
+            match self.send_with_timeout(cmd, timeout_ms) {
+                Err(Error::CmeError(e))
+                    if attempt < A::ATTEMPTS && A::RETRIABLE_CME_ERRORS.contains(&e) =>
+                {
+                    debug!("Retrying on {:?}", e);
+                }
+                Err(e) if A::is_retriable_error(&e) => {
+                    debug!("Retrying on {:?}", e);
+                    error = e;
+                }
                 r => return r,
             }
         }
-        Err(Error::Timeout)
+        Err(error)
     }
 }
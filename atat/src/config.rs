@@ -1,5 +1,9 @@
+use core::fmt::Write;
+
 use embassy_time::{Duration, Instant};
 
+use crate::{AtatCmd, Error};
+
 /// Configuration of both the ingress manager, and the AT client. Some of these
 /// parameters can be changed on the fly, through issuing a [`Command`] from the
 /// client.
@@ -11,6 +15,14 @@ pub struct Config {
     pub(crate) tx_timeout: Duration,
     pub(crate) flush_timeout: Duration,
     pub(crate) get_response_timeout: GetTimeout,
+    pub(crate) echo_enabled: bool,
+    pub(crate) wakeup_bytes: Option<&'static [u8]>,
+    pub(crate) wakeup_delay: Duration,
+    pub(crate) keepalive_interval: Option<Duration>,
+    pub(crate) keepalive_cmd: &'static [u8],
+    pub(crate) keepalive_timeout: Duration,
+    pub(crate) default_termination: &'static str,
+    pub(crate) append_sequence: bool,
 }
 
 pub type GetTimeout = fn(Instant, Duration) -> Instant;
@@ -33,6 +45,14 @@ impl Config {
             tx_timeout: Duration::from_millis(1000),
             flush_timeout: Duration::from_millis(1000),
             get_response_timeout,
+            echo_enabled: true,
+            wakeup_bytes: None,
+            wakeup_delay: Duration::from_millis(0),
+            keepalive_interval: None,
+            keepalive_cmd: b"AT",
+            keepalive_timeout: Duration::from_secs(1),
+            default_termination: "\r\n",
+            append_sequence: false,
         }
     }
 
@@ -65,4 +85,225 @@ impl Config {
         self.get_response_timeout = compute;
         self
     }
+
+    /// Set whether the modem echoes commands back (`ATE1`). If disabled, the
+    /// digester will never attempt to strip an echo from incoming data.
+    #[must_use]
+    pub const fn echo_enabled(mut self, enabled: bool) -> Self {
+        self.echo_enabled = enabled;
+        self
+    }
+
+    /// Set a sequence of bytes to be transmitted ahead of every command, in
+    /// order to wake the modem from sleep, eg. `b"AT\r"`.
+    #[must_use]
+    pub const fn wakeup_bytes(mut self, bytes: &'static [u8]) -> Self {
+        self.wakeup_bytes = Some(bytes);
+        self
+    }
+
+    /// Set the delay between transmitting the wakeup bytes and the command.
+    #[must_use]
+    pub const fn wakeup_delay(mut self, duration: Duration) -> Self {
+        self.wakeup_delay = duration;
+        self
+    }
+
+    /// Set the idle interval after which `Client::maybe_keepalive` transmits
+    /// the keepalive command.
+    #[must_use]
+    pub const fn keepalive(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Set the raw keepalive command, excluding termination. The command is
+    /// terminated with the configured [`Config::default_termination`].
+    ///
+    /// **default**: `b"AT"`
+    #[must_use]
+    pub const fn keepalive_cmd(mut self, cmd: &'static [u8]) -> Self {
+        self.keepalive_cmd = cmd;
+        self
+    }
+
+    /// Set the time to wait for the response to the keepalive command.
+    ///
+    /// **default**: 1 s
+    #[must_use]
+    pub const fn keepalive_timeout(mut self, duration: Duration) -> Self {
+        self.keepalive_timeout = duration;
+        self
+    }
+
+    /// Set the termination of commands not specifying one of their own, see
+    /// [`AtatCmd::DEFAULT_TERMINATION`].
+    ///
+    /// **default**: `"\r\n"`
+    #[must_use]
+    pub const fn default_termination(mut self, termination: &'static str) -> Self {
+        self.default_termination = termination;
+        self
+    }
+
+    /// Set whether the client appends a sequence number to every command,
+    /// as `,<seq>` ahead of the termination, for protocols requiring one.
+    ///
+    /// The sequence number is managed by the client, starting at 0 and
+    /// wrapping around after 255.
+    ///
+    /// Commands ending in an NMEA style checksum `*HH` get the sequence
+    /// number inserted ahead of the checksum, which is updated to cover it.
+    ///
+    /// **default**: `false`
+    #[must_use]
+    pub const fn append_sequence(mut self, enabled: bool) -> Self {
+        self.append_sequence = enabled;
+        self
+    }
+
+    /// Replace the default termination of the command written to
+    /// `buf[..len]` with the configured one, returning the new length.
+    ///
+    /// Fails with [`Error::Overflow`] if `buf` has no room for the configured
+    /// termination.
+    pub(crate) fn apply_default_termination<Cmd: AtatCmd>(
+        &self,
+        buf: &mut [u8],
+        len: usize,
+    ) -> Result<usize, Error> {
+        let Some(termination) = Cmd::DEFAULT_TERMINATION else {
+            return Ok(len);
+        };
+        if !buf[..len].ends_with(termination.as_bytes()) {
+            return Ok(len);
+        }
+
+        let start = len - termination.len();
+        let replacement = self.default_termination.as_bytes();
+        buf.get_mut(start..start + replacement.len())
+            .ok_or(Error::Overflow)?
+            .copy_from_slice(replacement);
+        Ok(start + replacement.len())
+    }
+
+    /// Insert the sequence number `seq` ahead of the termination of the
+    /// command written to `buf[..len]` and advance it, if enabled, returning
+    /// the new length.
+    ///
+    /// Fails with [`Error::Overflow`], leaving `seq` untouched, if `buf` has
+    /// no room for the sequence number.
+    pub(crate) fn apply_sequence(
+        &self,
+        buf: &mut [u8],
+        len: usize,
+        seq: &mut u8,
+    ) -> Result<usize, Error> {
+        if !self.append_sequence {
+            return Ok(len);
+        }
+
+        let mut suffix = heapless::String::<4>::new();
+        let _ = write!(suffix, ",{}", seq);
+
+        let end = len + suffix.len();
+        if end > buf.len() {
+            return Err(Error::Overflow);
+        }
+        let mut body = buf[..len]
+            .iter()
+            .rposition(|&c| c != b'\r' && c != b'\n')
+            .map_or(0, |i| i + 1);
+
+        // The checksum is the XOR of the checksummed bytes, so covering the
+        // inserted sequence number only takes folding it into the checksum.
+        let checksum = body
+            .checked_sub(3)
+            .filter(|&i| buf[i] == b'*' && buf[i + 1..body].iter().all(u8::is_ascii_hexdigit))
+            .and_then(|i| {
+                let hex = core::str::from_utf8(&buf[i + 1..body]).ok()?;
+                Some((i, u8::from_str_radix(hex, 16).ok()?))
+            });
+        if let Some((start, checksum)) = checksum {
+            body = start;
+            let checksum = suffix.bytes().fold(checksum, |acc, b| acc ^ b);
+            let mut hex = heapless::String::<2>::new();
+            let _ = write!(hex, "{:02X}", checksum);
+            buf[start + 1..start + 3].copy_from_slice(hex.as_bytes());
+        }
+
+        buf.copy_within(body..len, body + suffix.len());
+        buf[body..body + suffix.len()].copy_from_slice(suffix.as_bytes());
+        *seq = seq.wrapping_add(1);
+        Ok(end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoResponse;
+
+    impl crate::AtatResp for NoResponse {}
+
+    struct Cmd;
+
+    impl AtatCmd for Cmd {
+        type Response = NoResponse;
+        const DEFAULT_TERMINATION: Option<&'static str> = Some("\r");
+
+        fn write(&self, buf: &mut [u8]) -> usize {
+            buf[..7].copy_from_slice(b"AT+CMD\r");
+            7
+        }
+
+        fn parse(&self, _resp: Result<&[u8], crate::InternalError>) -> Result<NoResponse, Error> {
+            Ok(NoResponse)
+        }
+    }
+
+    #[test]
+    fn apply_default_termination_overflow() {
+        let config = Config::new();
+
+        let mut buf = *b"AT+CMD\r\0";
+        assert_eq!(Ok(8), config.apply_default_termination::<Cmd>(&mut buf, 7));
+        assert_eq!(b"AT+CMD\r\n", &buf);
+
+        let mut buf = *b"AT+CMD\r";
+        assert_eq!(
+            Err(Error::Overflow),
+            config.apply_default_termination::<Cmd>(&mut buf, 7)
+        );
+    }
+
+    #[test]
+    fn apply_sequence_overflow() {
+        let config = Config::new().append_sequence(true);
+        let mut seq = 7;
+
+        let mut buf = *b"AT+CMD\r\0\0";
+        assert_eq!(Ok(9), config.apply_sequence(&mut buf, 7, &mut seq));
+        assert_eq!(b"AT+CMD,7\r", &buf);
+        assert_eq!(8, seq);
+
+        let mut buf = *b"AT+CMD\r\0";
+        assert_eq!(
+            Err(Error::Overflow),
+            config.apply_sequence(&mut buf, 7, &mut seq)
+        );
+        assert_eq!(8, seq);
+    }
+
+    #[test]
+    fn apply_sequence_nmea_checksum() {
+        let config = Config::new().append_sequence(true);
+        let mut seq = 7;
+
+        let mut buf = [0; 24];
+        buf[..18].copy_from_slice(b"$PMTK220,1000*1F\r\n");
+        assert_eq!(Ok(20), config.apply_sequence(&mut buf, 18, &mut seq));
+        assert_eq!(b"$PMTK220,1000,7*04\r\n", &buf[..20]);
+    }
 }
@@ -4,9 +4,10 @@ mod tests {
 
     use crate as atat;
     use atat::AtatCmd;
-    use atat_derive::{AtatCmd, AtatEnum, AtatResp};
+    use atat_derive::{AtatCmd, AtatEnum, AtatResp, AtatUrc};
     use heapless::{String, Vec};
-    use serde_at::{from_str, to_string, SerializeOptions};
+    use serde_at::de::trailing_discriminant::TrailingDiscriminant;
+    use serde_at::{from_str, serde, to_string, SerializeOptions};
 
     macro_rules! assert_not_impl {
         ($x:ty, $($t:path),+ $(,)*) => {
@@ -70,6 +71,95 @@ mod tests {
         SingleSimpleTupleLifetime(&'a str),
     }
 
+    #[derive(Debug, PartialEq, AtatEnum)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[at_enum(u8, no_discriminant)]
+    enum NoDiscriminantEnum {
+        #[at_arg(value = 0)]
+        Unit,
+        #[at_arg(value = 1)]
+        Variant(u8),
+        #[at_arg(value = 2)]
+        Tuple(u8, String<10>),
+        #[at_arg(value = 3)]
+        Struct { x: u8, y: u16 },
+    }
+
+    #[derive(Debug, PartialEq, AtatEnum)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    enum TrailingEnum {
+        #[at_arg(value = 0)]
+        Unit,
+        #[at_arg(value = 1)]
+        Value(u8),
+    }
+
+    #[derive(Debug, PartialEq, AtatEnum)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[allow(clippy::upper_case_acronyms)]
+    enum Functionality {
+        #[at_arg(value = 0)]
+        Min,
+        #[at_arg(value = 1)]
+        Full,
+        #[at_arg(value = 4)]
+        APM,
+        #[at_arg(value = 6)]
+        DM,
+    }
+
+    #[derive(Debug, PartialEq, AtatResp)]
+    struct ModuleFunctionality {
+        fun: Functionality,
+    }
+
+    #[derive(Debug, PartialEq, AtatResp)]
+    struct TrailingResponse {
+        a: u8,
+        kind: TrailingDiscriminant<TrailingEnum>,
+    }
+
+    #[derive(Debug, PartialEq, AtatResp)]
+    struct TrailingFieldResponse {
+        kind: TrailingDiscriminant<TrailingEnum>,
+        b: u8,
+    }
+
+    #[derive(Debug, Clone, AtatResp)]
+    struct MqttMessage {
+        client: u8,
+        msg_id: u16,
+        topic: String<32>,
+        payload: serde_at::de::length_delimited::LengthDelimited<32>,
+    }
+
+    #[derive(Debug, Clone, AtatUrc)]
+    enum MqttUrc {
+        #[at_urc("+QMTRECV", parse = atat::digest::parser::length_delimited_urc_helper)]
+        Received(MqttMessage),
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CMD", NoResponse)]
+    struct OnOffCmd {
+        #[at_arg(bool_as = "onoff")]
+        enabled: bool,
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+COPS", NoResponse)]
+    struct SetOperatorSelection {
+        mode: u8,
+        #[at_arg(depends_on = Self::is_manual)]
+        format: u8,
+    }
+
+    impl SetOperatorSelection {
+        fn is_manual(&self) -> bool {
+            self.mode == 1
+        }
+    }
+
     #[derive(Debug, PartialEq, AtatCmd)]
     #[at_cmd("+CFUN", NoResponse)]
     struct LengthTester<'a> {
@@ -83,6 +173,50 @@ mod tests {
         // d: Vec<SimpleEnumU32, 5>,
     }
 
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("SLED", NoResponse, cmd_prefix = Self::prefix)]
+    struct SetLed {
+        mode: u8,
+    }
+
+    impl SetLed {
+        /// Modes from 10 and up are vendor specific, set through `AT#SLED`
+        fn prefix(&self) -> &str {
+            if self.mode >= 10 {
+                "AT#"
+            } else {
+                "AT+"
+            }
+        }
+    }
+
+    #[test]
+    fn test_runtime_cmd_prefix() {
+        let mut buf = [0; 32];
+        let cmd = SetLed { mode: 12 };
+        assert_eq!(cmd.cmd_prefix(), "AT#");
+        let len = cmd.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT#SLED=12\r");
+
+        let cmd = SetLed { mode: 2 };
+        assert_eq!(cmd.cmd_prefix(), "AT+");
+        let len = cmd.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT+SLED=2\r");
+
+        // The prefix given as a string is reported as well
+        assert_eq!(OnOffCmd { enabled: true }.cmd_prefix(), "AT");
+    }
+
+    #[test]
+    fn test_depends_on() {
+        let mut buf = [0; 32];
+        let len = SetOperatorSelection { mode: 1, format: 2 }.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT+COPS=1,2\r");
+
+        let len = SetOperatorSelection { mode: 0, format: 2 }.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT+COPS=0\r");
+    }
+
     #[test]
     fn test_length_serialize() {
         let mut buf = [0; 360];
@@ -103,6 +237,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bool_as_onoff() {
+        let mut buf = [0; 32];
+        let len = OnOffCmd { enabled: true }.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT+CMD=\"ON\"\r");
+
+        let len = OnOffCmd { enabled: false }.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT+CMD=\"OFF\"\r");
+    }
+
     #[test]
     fn test_mixed_enum() {
         assert_not_impl!(MixedEnum, TryFrom<u8>);
@@ -184,6 +328,269 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_no_discriminant_enum() {
+        assert_eq!(
+            to_string::<_, 10>(
+                &NoDiscriminantEnum::Variant(5),
+                "CMD",
+                SerializeOptions::default()
+            )
+            .unwrap(),
+            String::<10>::try_from("5").unwrap()
+        );
+        assert_eq!(
+            to_string::<_, 20>(
+                &NoDiscriminantEnum::Tuple(5, String::try_from("data").unwrap()),
+                "CMD",
+                SerializeOptions::default()
+            )
+            .unwrap(),
+            String::<20>::try_from("5,\"data\"").unwrap()
+        );
+        assert_eq!(
+            to_string::<_, 10>(
+                &NoDiscriminantEnum::Struct { x: 1, y: 300 },
+                "CMD",
+                SerializeOptions::default()
+            )
+            .unwrap(),
+            String::<10>::try_from("1,300").unwrap()
+        );
+        assert_eq!(
+            to_string::<_, 10>(
+                &NoDiscriminantEnum::Unit,
+                "CMD",
+                SerializeOptions::default()
+            )
+            .unwrap(),
+            String::<10>::try_from("0").unwrap()
+        );
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CFUN?", ModuleFunctionality)]
+    struct GetModuleFunctionality;
+
+    /// SIM states reported by `+CPIN?`, matched on their string representation
+    #[derive(Debug, PartialEq)]
+    enum PinStatusCode {
+        Ready,
+        SimPin,
+        SimPuk,
+        PhSimPin,
+    }
+
+    impl<'de> serde::Deserialize<'de> for PinStatusCode {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct CodeVisitor;
+
+            impl serde::de::Visitor<'_> for CodeVisitor {
+                type Value = PinStatusCode;
+
+                fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.write_str("a SIM state")
+                }
+
+                fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    self.visit_bytes(v.as_bytes())
+                }
+
+                fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                    match v {
+                        b"READY" => Ok(PinStatusCode::Ready),
+                        b"SIM PIN" => Ok(PinStatusCode::SimPin),
+                        b"SIM PUK" => Ok(PinStatusCode::SimPuk),
+                        b"PH-SIM PIN" => Ok(PinStatusCode::PhSimPin),
+                        _ => Err(E::custom("unknown SIM state")),
+                    }
+                }
+            }
+
+            deserializer.deserialize_identifier(CodeVisitor)
+        }
+    }
+
+    #[derive(Debug, PartialEq, AtatResp)]
+    struct PinStatus {
+        code: PinStatusCode,
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CPIN?", PinStatus)]
+    struct GetPinStatus;
+
+    #[test]
+    fn test_pin_status() {
+        let cases: [(&[u8], PinStatusCode); 7] = [
+            (b"+CPIN: READY", PinStatusCode::Ready),
+            (b"+CPIN: \"READY\"", PinStatusCode::Ready),
+            (b"+CPIN: SIM PIN", PinStatusCode::SimPin),
+            (b"+CPIN: \"SIM PIN\"", PinStatusCode::SimPin),
+            (b"+CPIN: SIM PUK", PinStatusCode::SimPuk),
+            (b"+CPIN: PH-SIM PIN", PinStatusCode::PhSimPin),
+            (b"+CPIN: \"PH-SIM PIN\"", PinStatusCode::PhSimPin),
+        ];
+        for (resp, code) in cases {
+            assert_eq!(GetPinStatus.parse(Ok(resp)), Ok(PinStatus { code }));
+        }
+        assert_eq!(
+            GetPinStatus.parse(Ok(b"+CPIN: SIM PIN2")),
+            Err(atat::Error::Parse)
+        );
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CFUN?", ModuleFunctionality, expected_response_prefix = "+CFUN:")]
+    struct GetModuleFunctionalityChecked;
+
+    #[test]
+    fn test_expected_response_prefix() {
+        assert_eq!(
+            GetModuleFunctionalityChecked.parse(Ok(b"+CFUN: 1")),
+            Ok(ModuleFunctionality {
+                fun: Functionality::Full
+            })
+        );
+        assert_eq!(
+            GetModuleFunctionalityChecked.parse(Ok(b"+CSQ: 1,99")),
+            Err(atat::Error::InvalidResponse)
+        );
+        assert_eq!(
+            GetModuleFunctionalityChecked.parse(Ok(b"")),
+            Err(atat::Error::InvalidResponse)
+        );
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CFUN?", ModuleFunctionality, space_delimited_token = true)]
+    struct GetModuleFunctionalitySpaced;
+
+    #[test]
+    fn test_space_delimited_token() {
+        assert_eq!(
+            GetModuleFunctionalitySpaced.parse(Ok(b"+CFUN 1")),
+            Ok(ModuleFunctionality {
+                fun: Functionality::Full
+            })
+        );
+        assert_eq!(
+            GetModuleFunctionalitySpaced.parse(Ok(b"+CFUN: 4")),
+            Ok(ModuleFunctionality {
+                fun: Functionality::APM
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        use atat::{parse_response, DigestResult, Error, InternalError};
+
+        assert_eq!(
+            parse_response(
+                &GetModuleFunctionality,
+                &DigestResult::Response(Ok(b"+CFUN: 4"))
+            ),
+            Some(Ok(ModuleFunctionality {
+                fun: Functionality::APM
+            }))
+        );
+        assert_eq!(
+            parse_response(
+                &GetModuleFunctionality,
+                &DigestResult::Response(Err(InternalError::Error))
+            ),
+            Some(Err(Error::Error))
+        );
+        assert_eq!(
+            parse_response(&GetModuleFunctionality, &DigestResult::Urc(b"+CFUN: 4")),
+            None
+        );
+        assert_eq!(
+            parse_response(&GetModuleFunctionality, &DigestResult::None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_non_sequential_enum_values() {
+        assert_eq!(
+            Ok(ModuleFunctionality {
+                fun: Functionality::APM
+            }),
+            from_str::<ModuleFunctionality>("+CFUN: 4")
+        );
+        assert_eq!(
+            Ok(ModuleFunctionality {
+                fun: Functionality::DM
+            }),
+            from_str::<ModuleFunctionality>("+CFUN: 6")
+        );
+        assert!(from_str::<ModuleFunctionality>("+CFUN: 2").is_err());
+
+        assert_eq!(
+            to_string::<_, 10>(&Functionality::APM, "CMD", SerializeOptions::default()).unwrap(),
+            String::<10>::try_from("4").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_trailing_discriminant_enum() {
+        assert_eq!(
+            Ok(TrailingDiscriminant(TrailingEnum::Value(5))),
+            from_str::<TrailingDiscriminant<TrailingEnum>>("+CMD: 5,1")
+        );
+        assert_eq!(
+            Ok(TrailingResponse {
+                a: 3,
+                kind: TrailingDiscriminant(TrailingEnum::Value(5)),
+            }),
+            from_str::<TrailingResponse>("+CMD: 3,5,1")
+        );
+        assert_eq!(
+            Ok(TrailingDiscriminant(TrailingEnum::Unit)),
+            from_str::<TrailingDiscriminant<TrailingEnum>>("+CMD: 0")
+        );
+        assert_eq!(
+            Ok(TrailingFieldResponse {
+                kind: TrailingDiscriminant(TrailingEnum::Unit),
+                b: 7,
+            }),
+            from_str::<TrailingFieldResponse>("+CMD: 0,7")
+        );
+        assert_eq!(
+            Ok(TrailingFieldResponse {
+                kind: TrailingDiscriminant(TrailingEnum::Value(5)),
+                b: 7,
+            }),
+            from_str::<TrailingFieldResponse>("+CMD: 5,1,7")
+        );
+    }
+
+    #[test]
+    fn test_length_delimited_urc() {
+        let buf = b"\r\n+QMTRECV: 0,1,\"topic\",7,hel\r\nlo\r\n";
+
+        assert_eq!(
+            Err(atat::digest::ParseError::Incomplete),
+            <MqttUrc as atat::Parser>::parse(&buf[..22])
+        );
+
+        let (urc, len) = <MqttUrc as atat::Parser>::parse(buf).unwrap();
+        assert_eq!(len, buf.len());
+        assert_eq!(urc, b"+QMTRECV: 0,1,\"topic\",7,hel\r\nlo");
+
+        let MqttUrc::Received(msg) = <MqttUrc as atat::AtatUrc>::parse(urc).unwrap();
+        assert_eq!(msg.client, 0);
+        assert_eq!(msg.msg_id, 1);
+        assert_eq!(msg.topic.as_str(), "topic");
+        assert_eq!(msg.payload.len, 7);
+        assert_eq!(&msg.payload.bytes[..], b"hel\r\nlo");
+    }
+
     fn custom_parse(response: &[u8]) -> Result<CustomResponseParse, atat::Error> {
         Ok(CustomResponseParse {
             arg1: core::str::from_utf8(&response[6..])
@@ -203,6 +610,28 @@ mod tests {
     #[at_cmd("+CFUN", CustomResponseParse, parse = custom_parse)]
     struct RequestWithCustomResponseParse;
 
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CSQ", NoResponse, no_echo = true)]
+    struct NoEchoRequest;
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+USORD", NoResponse, response_len = 512)]
+    struct LargeResponseRequest;
+
+    #[test]
+    fn test_response_len() {
+        assert_eq!(LargeResponseRequest::EXPECTED_RESPONSE_LEN, Some(512));
+        assert_eq!(NoEchoRequest::EXPECTED_RESPONSE_LEN, None);
+    }
+
+    #[test]
+    fn test_no_echo() {
+        assert_eq!(
+            (NoEchoRequest::EXPECTS_ECHO, LengthTester::EXPECTS_ECHO),
+            (false, true)
+        );
+    }
+
     #[test]
     fn test_custom_parse() {
         assert_eq!(
@@ -210,4 +639,244 @@ mod tests {
             Ok(CustomResponseParse { arg1: 123 })
         );
     }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("", NoResponse, termination = "\r\n")]
+    struct Keepalive;
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("", NoResponse, value_sep = false, termination = "\r\n")]
+    struct RawParams {
+        a: u8,
+        b: u8,
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("", NoResponse, termination = "\r\n")]
+    struct RawParamsDefaultSep {
+        a: u8,
+    }
+
+    #[test]
+    fn test_empty_cmd() {
+        let mut buf = [0; 16];
+        let len = Keepalive.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT\r\n");
+
+        let len = RawParams { a: 1, b: 2 }.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT1,2\r\n");
+
+        // An empty command never gets a value separator
+        let len = RawParamsDefaultSep { a: 0 }.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT0\r\n");
+    }
+
+    #[derive(Debug, PartialEq, AtatResp)]
+    struct CallEntry {
+        id: u8,
+        dir: u8,
+        stat: u8,
+        mode: u8,
+        mpty: u8,
+        number: Option<String<32>>,
+        number_type: Option<u8>,
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CLCC", Vec<CallEntry, 4>)]
+    struct ListCurrentCalls;
+
+    #[test]
+    fn test_multi_record_response() {
+        let calls = ListCurrentCalls
+            .parse(Ok(
+                b"+CLCC: 1,0,0,0,0,\"+4512345678\",145\r\n+CLCC: 2,1,5,0,0,\"87654321\",129",
+            ))
+            .unwrap();
+
+        assert_eq!(
+            calls,
+            [
+                CallEntry {
+                    id: 1,
+                    dir: 0,
+                    stat: 0,
+                    mode: 0,
+                    mpty: 0,
+                    number: Some(String::try_from("+4512345678").unwrap()),
+                    number_type: Some(145),
+                },
+                CallEntry {
+                    id: 2,
+                    dir: 1,
+                    stat: 5,
+                    mode: 0,
+                    mpty: 0,
+                    number: Some(String::try_from("87654321").unwrap()),
+                    number_type: Some(129),
+                },
+            ]
+        );
+
+        // Entries without a number
+        let calls = ListCurrentCalls
+            .parse(Ok(b"+CLCC: 1,0,0,0,0\r\n+CLCC: 2,1,5,0,0"))
+            .unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].number, None);
+        assert_eq!(calls[1].id, 2);
+    }
+
+    #[derive(Debug, Clone, PartialEq, AtatResp)]
+    struct ContextActivation {
+        cid: u8,
+        stat: u8,
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("#SGACT?", Vec<ContextActivation, 4>)]
+    struct GetContextActivation;
+
+    #[derive(Debug, Clone, PartialEq, AtatUrc)]
+    enum TelitUrc {
+        #[at_urc("#SGACT")]
+        ContextActivation(ContextActivation),
+    }
+
+    #[test]
+    fn test_hash_prefixed_response() {
+        let mut buf = [0; 32];
+        let len = GetContextActivation.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT#SGACT?\r");
+
+        assert_eq!(
+            GetContextActivation.parse(Ok(b"#SGACT: 1,1\r\n#SGACT: 2,0")),
+            Ok(Vec::<_, 4>::from_slice(&[
+                ContextActivation { cid: 1, stat: 1 },
+                ContextActivation { cid: 2, stat: 0 },
+            ])
+            .unwrap())
+        );
+    }
+
+    #[test]
+    fn test_hash_prefixed_urc() {
+        use atat::{DigestResult, Digester};
+
+        let mut digester = crate::AtDigester::<TelitUrc>::new();
+        assert_eq!(
+            digester.digest(b"\r\n#SGACT: 1,0\r\n"),
+            (DigestResult::Urc(b"#SGACT: 1,0"), 15)
+        );
+
+        assert_eq!(
+            <TelitUrc as atat::AtatUrc>::parse(b"#SGACT: 1,0"),
+            Some(TelitUrc::ContextActivation(ContextActivation {
+                cid: 1,
+                stat: 0
+            }))
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct PendingMessages(Option<u8>);
+
+    impl atat::AtatResp for PendingMessages {}
+
+    fn parse_pending_messages(response: &[u8]) -> Result<PendingMessages, atat::Error> {
+        match response {
+            // A bare `OK`: accepted, nothing to report
+            [] => Ok(PendingMessages(None)),
+            _ => core::str::from_utf8(response)
+                .ok()
+                .and_then(|r| r.strip_prefix("+CMGL: "))
+                .and_then(|count| count.parse().ok())
+                .map(|count| PendingMessages(Some(count)))
+                .ok_or(atat::Error::Parse),
+        }
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CMGL", PendingMessages, parse = parse_pending_messages)]
+    struct ListPendingMessages;
+
+    #[test]
+    fn test_empty_ok_response() {
+        use crate::digest::{DigestResult, Digester};
+
+        let mut digester = crate::AtDigester::<MqttUrc>::new();
+        let (DigestResult::Response(empty), _) = digester.digest(b"\r\nOK\r\n") else {
+            panic!("expected a response");
+        };
+        assert_eq!(empty, Ok(&b""[..]));
+        assert_eq!(ListPendingMessages.parse(empty), Ok(PendingMessages(None)));
+
+        let (DigestResult::Response(data), _) = digester.digest(b"\r\n+CMGL: 3\r\n\r\nOK\r\n")
+        else {
+            panic!("expected a response");
+        };
+        assert_eq!(
+            ListPendingMessages.parse(data),
+            Ok(PendingMessages(Some(3)))
+        );
+    }
+
+    /// Message reference returned by `+CMGS`, once the SMS has been sent
+    #[derive(Debug, PartialEq, AtatResp)]
+    struct MessageReference {
+        mr: u8,
+    }
+
+    /// Send an SMS in text mode. The text itself is written following the
+    /// `>` prompt, terminated by `Ctrl-Z`.
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CMGS", MessageReference, timeout_ms = 120000)]
+    struct SendSms<'a> {
+        da: &'a str,
+    }
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CSCLK?", atat::ScalarResponse<u8>)]
+    struct GetSleepMode;
+
+    #[derive(Debug, PartialEq, AtatCmd)]
+    #[at_cmd("+CGMR", atat::ScalarResponse<String<16>>)]
+    struct GetFirmwareVersion;
+
+    #[test]
+    fn test_scalar_response() {
+        assert_eq!(
+            Ok(atat::ScalarResponse(2)),
+            GetSleepMode.parse(Ok(b"+CSCLK: 2"))
+        );
+        assert_eq!(
+            Ok(atat::ScalarResponse(String::try_from("R1.2.3").unwrap())),
+            GetFirmwareVersion.parse(Ok(b"+CGMR: \"R1.2.3\""))
+        );
+        assert_eq!(
+            Err(atat::Error::Parse),
+            GetSleepMode.parse(Ok(b"+CSCLK: x"))
+        );
+    }
+
+    #[test]
+    fn test_message_reference() {
+        use crate::digest::{DigestResult, Digester};
+
+        let mut buf = [0; 32];
+        let len = SendSms { da: "+4512345678" }.write(&mut buf);
+        assert_eq!(&buf[..len], b"AT+CMGS=\"+4512345678\"\r");
+
+        // Echo of the text and `Ctrl-Z`, followed by the reference and `OK`
+        let mut digester = crate::AtDigester::<MqttUrc>::new();
+        let (DigestResult::Response(response), _) =
+            digester.digest(b"Hello\x1a\r\n+CMGS: 123\r\n\r\nOK\r\n")
+        else {
+            panic!("expected a response");
+        };
+        assert_eq!(
+            SendSms { da: "+4512345678" }.parse(response),
+            Ok(MessageReference { mr: 123 })
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use core::marker::PhantomData;
 
-use crate::InternalError;
+use crate::{AtatCmd, Error, InternalError};
 
 #[derive(Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -29,6 +29,44 @@ impl From<nom::Err<nom::error::Error<&[u8]>>> for ParseError {
 pub trait Digester {
     /// Digest the input buffer and return the result and the number of bytes consumed.
     fn digest<'a>(&mut self, buf: &'a [u8]) -> (DigestResult<'a>, usize);
+
+    /// Enable or disable stripping of command echo for the current response
+    /// window.
+    ///
+    /// This is called with `false` for commands that have
+    /// [`AtatCmd::EXPECTS_ECHO`](crate::AtatCmd::EXPECTS_ECHO) set to `false`,
+    /// and with `true` for all other commands. Digesters that do not handle
+    /// echo can ignore it.
+    fn set_echo_stripping(&mut self, _enabled: bool) {}
+
+    /// The line terminator framing the data digested, used by the ingress to
+    /// find the start of a frame when making room in a full buffer.
+    fn line_terminator(&self) -> &'static [u8] {
+        b"\r\n"
+    }
+
+    /// Called with bytes dropped by the ingress without being digested, eg.
+    /// to make room in a full buffer.
+    fn discard(&mut self, _garbage: &[u8]) {}
+
+    /// Digest the input buffer, only returning once a complete frame is
+    /// available.
+    ///
+    /// Unlike [`Digester::digest`], echo and whitespace preceding an
+    /// incomplete frame is not reported as consumed. `None` is returned until
+    /// the buffer holds a full frame, in which case the result is returned
+    /// together with the total number of bytes to consume, including any
+    /// leading echo and whitespace.
+    fn digest_complete<'a>(&mut self, buf: &'a [u8]) -> Option<(DigestResult<'a>, usize)> {
+        let mut offset = 0;
+        loop {
+            match self.digest(&buf[offset..]) {
+                (DigestResult::None, 0) => return None,
+                (DigestResult::None, swallowed) => offset += swallowed,
+                (result, swallowed) => return Some((result, offset + swallowed)),
+            }
+        }
+    }
 }
 
 pub trait Parser {
@@ -38,6 +76,18 @@ pub trait Parser {
     /// - if a URC exists but is incomplete, return [ParseError::Incomplete]
     /// - if a URC exists and is complete, return it and its length
     fn parse(buf: &[u8]) -> Result<(&[u8], usize), ParseError>;
+
+    /// Parse a URC from lines terminated by `le` rather than `\r\n`. See
+    /// [`AtDigester::with_line_terminator`].
+    ///
+    /// Defaults to [`Parser::parse`], for parsers only handling `\r\n`.
+    fn parse_with_terminator<'a>(
+        buf: &'a [u8],
+        le: &'static [u8],
+    ) -> Result<(&'a [u8], usize), ParseError> {
+        let _ = le;
+        Self::parse(buf)
+    }
 }
 
 /// A Digester that tries to implement the basic AT standard.
@@ -65,11 +115,30 @@ pub trait Parser {
 /// but can be others as well depending on manufacturer.
 ///
 /// Usually \<PROMPT> can be one of \['>', '@'], and is command specific and only valid for few selected commands.
+///
+/// Echo detection is a heuristic: any data preceding the first `\r\n` in the
+/// buffer is considered echo and discarded. For commands that are never echoed
+/// (see [`AtatCmd::EXPECTS_ECHO`](crate::AtatCmd::EXPECTS_ECHO)), the client
+/// disables this through [`Digester::set_echo_stripping`] for the duration of
+/// the response, so that a leading response line is not mistaken for echo.
 pub struct AtDigester<P: Parser> {
     _urc_parser: PhantomData<P>,
+    urc_parser: Option<fn(&[u8]) -> Result<(&[u8], usize), ParseError>>,
     custom_success: fn(&[u8]) -> Result<(&[u8], usize), ParseError>,
     custom_error: fn(&[u8]) -> Result<(&[u8], usize), ParseError>,
     custom_prompt: fn(&[u8]) -> Result<(u8, usize), ParseError>,
+    abort_token: Option<&'static [u8]>,
+    send_result_tokens: bool,
+    inline_result_codes: bool,
+    garbage_observer: fn(&[u8]),
+    max_response_len: Option<usize>,
+    max_echo_len: Option<usize>,
+    reboot_indicators: &'static [&'static [u8]],
+    reboot_observer: fn(&[u8]),
+    reboot_fails_pending: bool,
+    urc_preambles: &'static [&'static [u8]],
+    line_terminator: &'static [u8],
+    strip_echo: bool,
 }
 
 impl<P: Parser> AtDigester<P> {
@@ -77,9 +146,22 @@ impl<P: Parser> AtDigester<P> {
     pub const fn new() -> Self {
         Self {
             _urc_parser: PhantomData,
+            urc_parser: None,
             custom_success: |_| Err(ParseError::NoMatch),
             custom_error: |_| Err(ParseError::NoMatch),
             custom_prompt: |_| Err(ParseError::NoMatch),
+            abort_token: None,
+            send_result_tokens: false,
+            inline_result_codes: false,
+            garbage_observer: |_| {},
+            max_response_len: None,
+            max_echo_len: None,
+            reboot_indicators: &[],
+            reboot_observer: |_| {},
+            reboot_fails_pending: false,
+            urc_preambles: &[],
+            line_terminator: b"\r\n",
+            strip_echo: true,
         }
     }
 
@@ -106,6 +188,205 @@ impl<P: Parser> AtDigester<P> {
             ..self
         }
     }
+
+    /// Register the token emitted by the modem to acknowledge an aborted
+    /// command, eg. `ABORTED`. A line consisting of this token is reported as
+    /// [`InternalError::Aborted`], completing the pending command with
+    /// [`Error::Aborted`](crate::Error::Aborted).
+    #[must_use]
+    pub fn with_abort_token(self, token: &'static [u8]) -> Self {
+        Self {
+            abort_token: Some(token),
+            ..self
+        }
+    }
+
+    /// Register a function to be called with any unparseable data, that is
+    /// discarded in order to reach a valid frame following it, or dropped by
+    /// the ingress to make room in a full buffer.
+    #[must_use]
+    pub fn with_garbage_observer(self, f: fn(&[u8])) -> Self {
+        Self {
+            garbage_observer: f,
+            ..self
+        }
+    }
+
+    /// Match URCs using `f` rather than [`Parser::parse`] of `P`, eg. to pick
+    /// the URCs to recognize at runtime.
+    ///
+    /// `f` is handed the raw buffer, so it has to match the configured
+    /// [line terminator](AtDigester::with_line_terminator) itself.
+    #[must_use]
+    pub fn with_urc_parser(self, f: fn(&[u8]) -> Result<(&[u8], usize), ParseError>) -> Self {
+        Self {
+            urc_parser: Some(f),
+            ..self
+        }
+    }
+
+    /// Replace the URC matcher, eg. when the modem enters a mode emitting a
+    /// different set of URCs, without having to replace the digester. See
+    /// [`AtDigester::with_urc_parser`].
+    pub fn set_urc_parser(&mut self, f: fn(&[u8]) -> Result<(&[u8], usize), ParseError>) {
+        self.urc_parser = Some(f);
+    }
+
+    /// Replace the custom success matcher, eg. when switching the modem
+    /// between data and command mode. See [`AtDigester::with_custom_success`].
+    pub fn set_custom_success(&mut self, f: fn(&[u8]) -> Result<(&[u8], usize), ParseError>) {
+        self.custom_success = f;
+    }
+
+    /// Replace the custom error matcher. See
+    /// [`AtDigester::with_custom_error`].
+    pub fn set_custom_error(&mut self, f: fn(&[u8]) -> Result<(&[u8], usize), ParseError>) {
+        self.custom_error = f;
+    }
+
+    /// Replace the custom prompt matcher. See
+    /// [`AtDigester::with_custom_prompt`].
+    pub fn set_custom_prompt(&mut self, f: fn(&[u8]) -> Result<(u8, usize), ParseError>) {
+        self.custom_prompt = f;
+    }
+
+    /// Recognize the `SEND OK` and `SEND FAIL` final result codes, used by
+    /// the socket send commands of many modems in place of `OK` and `ERROR`.
+    ///
+    /// `SEND OK` completes the response successfully, while `SEND FAIL` is
+    /// reported as [`InternalError::Error`].
+    #[must_use]
+    pub fn with_send_result_tokens(self) -> Self {
+        Self {
+            send_result_tokens: true,
+            ..self
+        }
+    }
+
+    /// Recognize `OK` and `ERROR` trailing the data on the same line, eg.
+    /// `+CMD: 5 OK`, as emitted by some modems in place of a separate result
+    /// code line. The data preceding the result code is the response.
+    #[must_use]
+    pub fn with_inline_result_codes(self) -> Self {
+        Self {
+            inline_result_codes: true,
+            ..self
+        }
+    }
+
+    /// Limit the length of the data in a successful response. Responses
+    /// exceeding this are reported as [`InternalError::InvalidResponse`].
+    #[must_use]
+    pub fn with_max_response_len(self, len: usize) -> Self {
+        Self {
+            max_response_len: Some(len),
+            ..self
+        }
+    }
+
+    /// Limit the number of bytes stripped as echo. Any data preceding the
+    /// first `\r\n` beyond the limit is left for the other matchers, so that
+    /// a frame following a long run of noise without a `\r\n` is not
+    /// swallowed along with it.
+    #[must_use]
+    pub fn with_max_echo_len(self, len: usize) -> Self {
+        Self {
+            max_echo_len: Some(len),
+            ..self
+        }
+    }
+
+    /// Register banners emitted by the modem on reboot, eg. `RDY`,
+    /// `+CFUN: 1` or `*ATREADY: 1`. When a line starting with one of these is
+    /// received, `f` is called with the line, to let the application
+    /// re-initialize the modem, and the line is reported as a URC.
+    ///
+    /// NOTE: Like URCs, indicators are matched ahead of responses, so an
+    /// indicator such as `+CFUN: 1` is also matched in the response to
+    /// `AT+CFUN?`.
+    #[must_use]
+    pub fn with_reboot_indicators(
+        self,
+        indicators: &'static [&'static [u8]],
+        f: fn(&[u8]),
+    ) -> Self {
+        Self {
+            reboot_indicators: indicators,
+            reboot_observer: f,
+            ..self
+        }
+    }
+
+    /// Report reboot indicators as [`InternalError::Rebooted`] instead of a
+    /// URC, failing any pending command with
+    /// [`Error::Rebooted`](crate::Error::Rebooted) rather than leaving it to
+    /// time out. See [`AtDigester::with_reboot_indicators`].
+    ///
+    /// NOTE: The error is only delivered to a command awaiting its response.
+    /// An indicator received while no command is pending is dropped, after
+    /// calling the observer.
+    #[must_use]
+    pub fn with_reboot_failing_pending(self) -> Self {
+        Self {
+            reboot_fails_pending: true,
+            ..self
+        }
+    }
+
+    /// Register preamble lines emitted by the modem ahead of a URC, eg. the
+    /// `^SID: 3` routing line preceding `+CIEV: 7,1`. A line starting with
+    /// one of these is consumed before matching URCs, so that it is neither
+    /// mistaken for echo nor prevents the following URC from being matched.
+    #[must_use]
+    pub fn with_urc_preambles(self, preambles: &'static [&'static [u8]]) -> Self {
+        Self {
+            urc_preambles: preambles,
+            ..self
+        }
+    }
+
+    /// Terminate lines with `terminator` rather than `\r\n`, for modems
+    /// ending lines with eg. just `\n` or `\r`, or devices speaking a line
+    /// protocol framed by a vendor specific byte.
+    ///
+    /// The terminator applies to all matchers, eg. `\nOK\n` completes a
+    /// response, as well as to URCs parsed through
+    /// [`Parser::parse_with_terminator`], as implemented by the `AtatUrc`
+    /// derive. URC parsers given through `parse = ..` or
+    /// [`AtDigester::with_urc_parser`] have to match the terminator
+    /// themselves.
+    #[must_use]
+    pub fn with_line_terminator(self, terminator: &'static [u8]) -> Self {
+        assert!(!terminator.is_empty(), "Line terminator must not be empty");
+        Self {
+            line_terminator: terminator,
+            ..self
+        }
+    }
+}
+
+/// Returns the length of the first complete URC recognized by `P` in `buf`,
+/// excluding any data preceding it.
+///
+/// Unlike the number of bytes consumed when digesting a
+/// [`DigestResult::Urc`], this does not include any leading garbage, space or
+/// echo.
+pub fn urc_len<P: Parser>(buf: &[u8]) -> Option<usize> {
+    (0..buf.len()).find_map(|i| P::parse(&buf[i..]).ok().map(|(_, len)| len))
+}
+
+/// Parses the response to `cmd` from a digest result, the way the clients do.
+///
+/// Returns `None` for results that are not a response to the command, ie.
+/// [`DigestResult::Urc`], [`DigestResult::Prompt`] and [`DigestResult::None`].
+pub fn parse_response<A: AtatCmd>(
+    cmd: &A,
+    result: &DigestResult<'_>,
+) -> Option<Result<A::Response, Error>> {
+    match result {
+        DigestResult::Response(response) => Some(cmd.parse(response.clone())),
+        DigestResult::Urc(_) | DigestResult::Prompt(_) | DigestResult::None => None,
+    }
 }
 
 impl<P: Parser> Default for AtDigester<P> {
@@ -116,20 +397,67 @@ impl<P: Parser> Default for AtDigester<P> {
 
 impl<P: Parser> Digester for AtDigester<P> {
     fn digest<'a>(&mut self, input: &'a [u8]) -> (DigestResult<'a>, usize) {
+        let le = self.line_terminator;
+
         // 1. Optionally discard space and echo
         let buf = parser::trim_start_ascii_space(input);
         let space_bytes = input.len() - buf.len();
-        let (buf, space_and_echo_bytes) = match nom::combinator::opt(parser::echo)(buf) {
-            Ok((buf, echo)) => (buf, space_bytes + echo.unwrap_or_default().len()),
-            Err(nom::Err::Incomplete(_)) => return (DigestResult::None, 0),
-            Err(_) => panic!("NOM ERROR - opt(echo)"),
+        let (buf, space_and_echo_bytes) = if self.strip_echo {
+            match nom::combinator::opt(|i| parser::echo_with(i, le))(buf) {
+                Ok((_, echo)) => {
+                    let echo_len = echo
+                        .unwrap_or_default()
+                        .len()
+                        .min(self.max_echo_len.unwrap_or(usize::MAX));
+                    (&buf[echo_len..], space_bytes + echo_len)
+                }
+                Err(nom::Err::Incomplete(_)) => return (DigestResult::None, 0),
+                Err(_) => panic!("NOM ERROR - opt(echo)"),
+            }
+        } else {
+            (buf, space_bytes)
         };
 
+        // Skip any preamble line announcing the following URC
+        let mut buf = buf;
+        let mut space_and_echo_bytes = space_and_echo_bytes;
+        for preamble in self.urc_preambles {
+            match parser::urc_preamble_with::<_, nom::error::Error<&[u8]>>(*preamble, le)(buf) {
+                Ok((rest, line)) => {
+                    buf = rest;
+                    space_and_echo_bytes += line.len();
+                    break;
+                }
+                Err(nom::Err::Incomplete(_)) => return (DigestResult::None, space_and_echo_bytes),
+                _ => {}
+            }
+        }
+
         // Incomplete. Eat whitespace and echo and do nothing else.
         let incomplete = (DigestResult::None, space_and_echo_bytes);
 
-        // 2. Match for URC's
-        match P::parse(buf) {
+        // 2. Match for reboot indicators, and URC's
+        for indicator in self.reboot_indicators {
+            match parser::urc_helper_with::<_, nom::error::Error<&[u8]>>(*indicator, le)(buf) {
+                Ok((_, (line, len))) => {
+                    (self.reboot_observer)(line);
+                    let result = if self.reboot_fails_pending {
+                        DigestResult::Response(Err(InternalError::Rebooted))
+                    } else {
+                        DigestResult::Urc(line)
+                    };
+                    return (result, len + space_and_echo_bytes);
+                }
+                Err(nom::Err::Incomplete(_)) => return incomplete,
+                _ => {}
+            }
+        }
+
+        let urc = match self.urc_parser {
+            Some(urc_parser) => urc_parser(buf),
+            None => P::parse_with_terminator(buf, le),
+        };
+        match urc {
             Ok((urc, len)) => return (DigestResult::Urc(urc), len + space_and_echo_bytes),
             Err(ParseError::Incomplete) => return incomplete,
             _ => {}
@@ -148,8 +476,34 @@ impl<P: Parser> Digester for AtDigester<P> {
             _ => {}
         }
 
+        // Socket send result codes, if enabled
+        if self.send_result_tokens {
+            match parser::send_result_response_with(buf, le) {
+                Ok((_, (result, len))) => return (result, len + space_and_echo_bytes),
+                Err(nom::Err::Incomplete(_)) => return incomplete,
+                _ => {}
+            }
+        }
+
+        // Result codes inline with the data, if enabled
+        if self.inline_result_codes {
+            if let Ok((_, (result, len))) = parser::inline_result_response_with(buf, le) {
+                return (result, len + space_and_echo_bytes);
+            }
+        }
+
         // Generic success replies
-        match parser::success_response(buf) {
+        match parser::success_response_with(buf, le) {
+            Ok((_, (DigestResult::Response(Ok(response)), len)))
+                if self
+                    .max_response_len
+                    .is_some_and(|max_len| response.len() > max_len) =>
+            {
+                return (
+                    DigestResult::Response(Err(InternalError::InvalidResponse)),
+                    len + space_and_echo_bytes,
+                )
+            }
             Ok((_, (result, len))) => return (result, len + space_and_echo_bytes),
             Err(nom::Err::Incomplete(_)) => return incomplete,
             _ => {}
@@ -158,7 +512,14 @@ impl<P: Parser> Digester for AtDigester<P> {
         // Custom prompts for data replies first, if any
         match (self.custom_prompt)(buf) {
             Ok((response, len)) => {
-                return (DigestResult::Prompt(response), len + space_and_echo_bytes)
+                // Consume any trailing space following the prompt in the same
+                // round, rather than leaving it for a separate digest
+                let rest = buf.get(len..).unwrap_or_default();
+                let trailing = rest.len() - parser::trim_start_ascii_space(rest).len();
+                return (
+                    DigestResult::Prompt(response),
+                    len + trailing + space_and_echo_bytes,
+                );
             }
             Err(ParseError::Incomplete) => return incomplete,
             _ => {}
@@ -170,6 +531,15 @@ impl<P: Parser> Digester for AtDigester<P> {
         }
 
         // 4. Parse for error responses
+        // Acknowledgement of an aborted command, if configured
+        if let Some(token) = self.abort_token {
+            match parser::abort_response_with(buf, token, le) {
+                Ok((_, (result, len))) => return (result, len + space_and_echo_bytes),
+                Err(nom::Err::Incomplete(_)) => return incomplete,
+                _ => {}
+            }
+        }
+
         // Custom error matches first, if any
         match (self.custom_error)(buf) {
             Ok((response, len)) => {
@@ -183,21 +553,38 @@ impl<P: Parser> Digester for AtDigester<P> {
         }
 
         // Generic error matches
-        if let Ok((_, (result, len))) = parser::error_response(buf) {
+        if let Ok((_, (result, len))) = parser::error_response_with(buf, le) {
             return (result, len + space_and_echo_bytes);
         }
 
         // Handle '\r\n <Garbage> \r\n <Valid URC> \r\n' as parser::echo will only consume garbage BEFORE a \r\n
-        if buf.starts_with(b"\r\n") && buf.len() > 4 {
-            let (res, consumed) = self.digest(&buf[2..]);
+        if buf.starts_with(le) && buf.len() > 2 * le.len() {
+            let (res, consumed) = self.digest(&buf[le.len()..]);
             if res != DigestResult::None {
-                return (res, space_and_echo_bytes + 2 + consumed);
+                if let Ok((_, garbage)) = parser::echo_with(&buf[le.len()..], le) {
+                    if !garbage.is_empty() {
+                        (self.garbage_observer)(garbage);
+                    }
+                }
+                return (res, space_and_echo_bytes + le.len() + consumed);
             }
         }
 
         // No matches at all.
         incomplete
     }
+
+    fn set_echo_stripping(&mut self, enabled: bool) {
+        self.strip_echo = enabled;
+    }
+
+    fn line_terminator(&self) -> &'static [u8] {
+        self.line_terminator
+    }
+
+    fn discard(&mut self, garbage: &[u8]) {
+        (self.garbage_observer)(garbage);
+    }
 }
 
 pub mod parser {
@@ -211,7 +598,7 @@ pub mod parser {
         branch::alt,
         bytes::streaming::tag,
         character::complete,
-        combinator::{eof, map, map_res, recognize},
+        combinator::{eof, map, map_res, opt, recognize},
         error::ParseError,
         sequence::tuple,
         IResult,
@@ -221,31 +608,280 @@ pub mod parser {
     pub fn urc_helper<'a, T, Error: ParseError<&'a [u8]>>(
         token: T,
     ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], usize), Error>
+    where
+        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
+        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
+    {
+        urc_helper_with(token, b"\r\n")
+    }
+
+    /// Like [`urc_helper`], for lines terminated by `le` rather than `\r\n`.
+    pub fn urc_helper_with<'a, T, Error: ParseError<&'a [u8]>>(
+        token: T,
+        le: &'static [u8],
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], usize), Error>
     where
         &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
         T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
     {
         move |i| {
-            let (i, (le, urc_tag)) = tuple((
-                complete::line_ending,
+            let (i, (leading, urc_tag)) = tuple((
+                line_end(le),
                 recognize(alt((
-                    tuple((tag(token.clone()), tag(":"), take_until_including("\r\n"))),
+                    tuple((tag(token.clone()), tag(":"), take_until_including(le))),
                     tuple((
                         tag(token.clone()),
-                        tag("\r\n"),
+                        tag(le),
                         nom::combinator::success((&b""[..], &b""[..])),
                     )),
                 ))),
             ))(i)?;
 
-            Ok((i, (urc_tag.trim_ascii(), le.len() + urc_tag.len())))
+            Ok((i, (urc_tag.trim_ascii(), leading.len() + urc_tag.len())))
+        }
+    }
+
+    /// Matches a line ending, either `\r\n`, `\n` or `le`.
+    fn line_end<'a, Error: ParseError<&'a [u8]>>(
+        le: &'static [u8],
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], &'a [u8], Error> {
+        move |i| alt((complete::line_ending, nom::bytes::complete::tag(le)))(i)
+    }
+
+    /// Matches up to and including the first `word` framed by the line
+    /// ending `le`, eg. `\r\nOK\r\n`, or only preceded by it unless
+    /// `trailing`. Returns the data preceding the framed word, and the framed
+    /// word itself.
+    fn take_until_line<'a, Error: ParseError<&'a [u8]>>(
+        le: &'static [u8],
+        word: &'static [u8],
+        trailing: bool,
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], &'a [u8]), Error> {
+        move |i: &'a [u8]| {
+            let framed = |line: &[u8]| {
+                line.starts_with(le)
+                    && line[le.len()..].starts_with(word)
+                    && (!trailing || line[le.len() + word.len()..].starts_with(le))
+            };
+            let Some(start) = (0..i.len()).find(|&pos| framed(&i[pos..])) else {
+                return Err(nom::Err::Error(Error::from_error_kind(
+                    i,
+                    nom::error::ErrorKind::TakeUntil,
+                )));
+            };
+            let end = start + le.len() + word.len() + if trailing { le.len() } else { 0 };
+
+            Ok((&i[end..], (&i[..start], &i[start..end])))
+        }
+    }
+
+    /// Matches the equivalent of regex: "(\r\n)?{token}.*", leaving the
+    /// terminating `\r\n` to be matched as the start of the following line.
+    pub fn urc_preamble<'a, T, Error: ParseError<&'a [u8]>>(
+        token: T,
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], &'a [u8], Error>
+    where
+        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
+        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
+    {
+        urc_preamble_with(token, b"\r\n")
+    }
+
+    /// Like [`urc_preamble`], for lines terminated by `le` rather than `\r\n`.
+    pub fn urc_preamble_with<'a, T, Error: ParseError<&'a [u8]>>(
+        token: T,
+        le: &'static [u8],
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], &'a [u8], Error>
+    where
+        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
+        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
+    {
+        move |i| {
+            recognize(tuple((
+                opt(line_end(le)),
+                tag(token.clone()),
+                nom::bytes::streaming::take_until(le),
+            )))(i)
+        }
+    }
+
+    /// Matches the equivalent of regex: "\r\n{token}\d*(:.*)?\r\n", matching a
+    /// family of URCs sharing a stem, eg. both `+CEREG` and `+CEREG2`.
+    pub fn urc_helper_stem<'a, T, Error: ParseError<&'a [u8]>>(
+        token: T,
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], usize), Error>
+    where
+        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
+        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
+    {
+        urc_helper_stem_with(token, b"\r\n")
+    }
+
+    /// Like [`urc_helper_stem`], for lines terminated by `le` rather than
+    /// `\r\n`.
+    pub fn urc_helper_stem_with<'a, T, Error: ParseError<&'a [u8]>>(
+        token: T,
+        le: &'static [u8],
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], usize), Error>
+    where
+        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
+        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
+    {
+        move |i| {
+            let (i, (leading, urc_tag)) = tuple((
+                line_end(le),
+                recognize(tuple((
+                    tag(token.clone()),
+                    nom::character::streaming::digit0,
+                    alt((
+                        recognize(tuple((tag(":"), take_until_including(le)))),
+                        tag(le),
+                    )),
+                ))),
+            ))(i)?;
+
+            Ok((i, (urc_tag.trim_ascii(), leading.len() + urc_tag.len())))
+        }
+    }
+
+    /// Matches the equivalent of regex: "\r\n{token}:.*,{len},{payload}\r\n",
+    /// where `payload` is exactly `len` bytes, optionally surrounded by double
+    /// quotes not included in `len`.
+    ///
+    /// Unlike [`urc_helper`], this does not stop at the first `\r\n`, making
+    /// it suitable for URCs carrying a binary payload, e.g.
+    /// `+QMTRECV: 0,0,"topic",5,hello`. The payload can be parsed as the last
+    /// parameter using `serde_at::de::length_delimited::LengthDelimited`.
+    ///
+    /// The first numeric parameter whose declared length ends the URC exactly
+    /// is taken as the length of the payload.
+    pub fn length_delimited_urc_helper<'a, T, Error: ParseError<&'a [u8]>>(
+        token: T,
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], usize), Error>
+    where
+        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
+        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
+    {
+        length_delimited_urc_helper_with(token, b"\r\n")
+    }
+
+    /// Like [`length_delimited_urc_helper`], for lines terminated by `le`
+    /// rather than `\r\n`.
+    pub fn length_delimited_urc_helper_with<'a, T, Error: ParseError<&'a [u8]>>(
+        token: T,
+        le: &'static [u8],
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], usize), Error>
+    where
+        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
+        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
+    {
+        move |i| {
+            let (params, (leading, head)) = tuple((
+                line_end(le),
+                recognize(tuple((tag(token.clone()), tag(":")))),
+            ))(i)?;
+
+            let mut in_quotes = false;
+            let mut incomplete = false;
+            let mut field_start = 0;
+            for (pos, &c) in params.iter().enumerate() {
+                match c {
+                    b'"' => in_quotes = !in_quotes,
+                    b',' if !in_quotes => {
+                        let field = params[field_start..pos].trim_ascii();
+                        field_start = pos + 1;
+
+                        if field.is_empty() || !field.iter().all(u8::is_ascii_digit) {
+                            continue;
+                        }
+                        let Some(len) = core::str::from_utf8(field)
+                            .ok()
+                            .and_then(|s| usize::from_str(s).ok())
+                        else {
+                            continue;
+                        };
+
+                        let start = pos + 1;
+                        let quoted = params.get(start) == Some(&b'"');
+                        let end = start + len + if quoted { 2 } else { 0 };
+
+                        match params.get(end..end + le.len()) {
+                            Some(end_le)
+                                if end_le == le && (!quoted || params[end - 1] == b'"') =>
+                            {
+                                let start = leading.len();
+                                let urc_len = head.len() + end;
+                                return Ok((
+                                    &params[end + le.len()..],
+                                    (&i[start..start + urc_len], start + urc_len + le.len()),
+                                ));
+                            }
+                            None => incomplete = true,
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if incomplete || !params.windows(le.len()).any(|w| w == le) {
+                return Err(nom::Err::Incomplete(nom::Needed::Unknown));
+            }
+
+            Err(nom::Err::Error(Error::from_error_kind(
+                i,
+                nom::error::ErrorKind::Verify,
+            )))
+        }
+    }
+
+    /// Matches the equivalent of regex: "{token}\r\n" at the start of a line,
+    /// acknowledging an aborted command.
+    pub fn abort_response<'a>(
+        buf: &'a [u8],
+        token: &[u8],
+    ) -> IResult<&'a [u8], (DigestResult<'a>, usize)> {
+        abort_response_with(buf, token, b"\r\n")
+    }
+
+    /// Like [`abort_response`], for lines terminated by `le` rather than
+    /// `\r\n`.
+    pub fn abort_response_with<'a>(
+        buf: &'a [u8],
+        token: &[u8],
+        le: &'static [u8],
+    ) -> IResult<&'a [u8], (DigestResult<'a>, usize)> {
+        let (i, ((data, token), end)) = tuple((take_until_including(token), tag(le)))(buf)?;
+
+        if !data.is_empty() && !data.ends_with(&le[le.len() - 1..]) {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                buf,
+                nom::error::ErrorKind::Tag,
+            )));
         }
+
+        Ok((
+            i,
+            (
+                DigestResult::Response(Err(InternalError::Aborted)),
+                data.len() + token.len() + end.len(),
+            ),
+        ))
     }
 
     pub fn error_response(buf: &[u8]) -> IResult<&[u8], (DigestResult<'_>, usize)> {
+        error_response_with(buf, b"\r\n")
+    }
+
+    /// Like [`error_response`], for lines terminated by `le` rather than
+    /// `\r\n`.
+    pub fn error_response_with<'a>(
+        buf: &'a [u8],
+        le: &'static [u8],
+    ) -> IResult<&'a [u8], (DigestResult<'a>, usize)> {
         alt((
             // Matches the equivalent of regex: "\r\n\+CME ERROR:\s*(\d+)\r\n"
-            map(numeric_error("\r\n+CME ERROR:"), |(error_code, len)| {
+            map(numeric_error(le, b"+CME ERROR:"), |(error_code, len)| {
                 (
                     DigestResult::Response(Err(InternalError::CmeError(CmeError::from(
                         error_code,
@@ -254,7 +890,7 @@ pub mod parser {
                 )
             }),
             // Matches the equivalent of regex: "\r\n\+CMS ERROR:\s*(\d+)\r\n"
-            map(numeric_error("\r\n+CMS ERROR:"), |(error_code, len)| {
+            map(numeric_error(le, b"+CMS ERROR:"), |(error_code, len)| {
                 (
                     DigestResult::Response(Err(InternalError::CmsError(CmsError::from(
                         error_code,
@@ -264,7 +900,7 @@ pub mod parser {
             }),
             // Matches the equivalent of regex: "\r\n\+CME ERROR:\s*([^\n\r]+)\r\n"
             #[cfg(feature = "string_errors")]
-            map(string_error("\r\n+CME ERROR:"), |(error_msg, len)| {
+            map(string_error(le, b"+CME ERROR:"), |(error_msg, len)| {
                 (
                     DigestResult::Response(Err(InternalError::CmeError(CmeError::from_msg(
                         error_msg,
@@ -274,7 +910,7 @@ pub mod parser {
             }),
             // Matches the equivalent of regex: "\r\n\+CMS ERROR:\s*([^\n\r]+)\r\n"
             #[cfg(feature = "string_errors")]
-            map(string_error("\r\n+CMS ERROR:"), |(error_msg, len)| {
+            map(string_error(le, b"+CMS ERROR:"), |(error_msg, len)| {
                 (
                     DigestResult::Response(Err(InternalError::CmsError(CmsError::from_msg(
                         error_msg,
@@ -283,28 +919,31 @@ pub mod parser {
                 )
             }),
             // Matches the equivalent of regex: "\r\nMODEM ERROR:\s*(\d+)\r\n"
-            map(numeric_error("\r\nMODEM ERROR:"), |(_error_code, len)| {
+            map(numeric_error(le, b"MODEM ERROR:"), |(_error_code, len)| {
                 (
                     DigestResult::Response(Err(InternalError::CmeError(CmeError::Unknown))),
                     len,
                 )
             }),
-            map(generic_error(), |len| {
+            map(generic_error(le), |len| {
                 (DigestResult::Response(Err(InternalError::Error)), len)
             }),
-            map(connection_error(), |(err, len)| {
+            map(connection_error(le), |(err, len)| {
                 (
                     DigestResult::Response(Err(InternalError::ConnectionError(err))),
                     len,
                 )
             }),
             // Samsung Z810 may reply "NA" to report a not-available error
-            map(recognize(tag("\r\nNA\r\n")), |tag: &[u8]| {
-                (
-                    DigestResult::Response(Err(InternalError::CmeError(CmeError::NotAllowed))),
-                    tag.len(),
-                )
-            }),
+            map(
+                recognize(tuple((tag(le), tag("NA"), tag(le)))),
+                |tag: &[u8]| {
+                    (
+                        DigestResult::Response(Err(InternalError::CmeError(CmeError::NotAllowed))),
+                        tag.len(),
+                    )
+                },
+            ),
         ))(buf)
     }
 
@@ -331,34 +970,137 @@ pub mod parser {
         )))
     }
 
-    pub fn success_response(buf: &[u8]) -> IResult<&[u8], (DigestResult<'_>, usize)> {
-        let (i, ((data, tag), ws)) = alt((
-            tuple((
-                take_until_including("\r\nOK\r\n"),
-                nom::combinator::success(&b""[..]),
-            )),
-            tuple((
-                take_until_including("\r\nCONNECT\r\n"),
-                nom::combinator::success(&b""[..]),
-            )),
+    /// Matches the equivalent of regex: "\r\nCONNECT (\d+)\r\n", returning
+    /// any information lines preceding it, followed by the `CONNECT <rate>`
+    /// line, as response data.
+    fn connect_with_rate<'a>(
+        buf: &'a [u8],
+        le: &'static [u8],
+    ) -> IResult<&'a [u8], (DigestResult<'a>, usize)> {
+        let (i, (prefix_data, rate, le)) = tuple((
+            recognize(take_until_line(le, b"CONNECT ", false)),
+            complete::digit1,
+            tag(le),
         ))(buf)?;
 
+        let len = prefix_data.len() + rate.len();
+        Ok((
+            i,
+            (
+                DigestResult::Response(Ok(buf[..len].trim_ascii())),
+                len + le.len(),
+            ),
+        ))
+    }
+
+    /// Matches a success response code. For `CONNECT <rate>` the response data
+    /// ends with the `CONNECT <rate>` line, eg. `CONNECT 9600`, so that the
+    /// rate is the last word of the response data.
+    pub fn success_response(buf: &[u8]) -> IResult<&[u8], (DigestResult<'_>, usize)> {
+        success_response_with(buf, b"\r\n")
+    }
+
+    /// Like [`success_response`], for lines terminated by `le` rather than
+    /// `\r\n`.
+    pub fn success_response_with<'a>(
+        buf: &'a [u8],
+        le: &'static [u8],
+    ) -> IResult<&'a [u8], (DigestResult<'a>, usize)> {
+        let (i, (data, tag)) = match alt((
+            take_until_line(le, b"OK", true),
+            take_until_line(le, b"CONNECT", true),
+        ))(buf)
+        {
+            Ok(r) => r,
+            Err(nom::Err::Error(_)) => return connect_with_rate(buf, le),
+            Err(e) => return Err(e),
+        };
+
         Ok((
             i,
             (
                 DigestResult::Response(Ok(data.trim_ascii())),
-                data.len() + tag.len() + ws.len(),
+                data.len() + tag.len(),
             ),
         ))
     }
 
+    /// Matches the equivalent of regex: "\r\nSEND (OK|FAIL)\r\n"
+    pub fn send_result_response(buf: &[u8]) -> IResult<&[u8], (DigestResult<'_>, usize)> {
+        send_result_response_with(buf, b"\r\n")
+    }
+
+    /// Like [`send_result_response`], for lines terminated by `le` rather
+    /// than `\r\n`.
+    pub fn send_result_response_with<'a>(
+        buf: &'a [u8],
+        le: &'static [u8],
+    ) -> IResult<&'a [u8], (DigestResult<'a>, usize)> {
+        alt((
+            map(
+                take_until_line(le, b"SEND OK", true),
+                |(data, tag): (&[u8], &[u8])| {
+                    (
+                        DigestResult::Response(Ok(data.trim_ascii())),
+                        data.len() + tag.len(),
+                    )
+                },
+            ),
+            map(
+                take_until_line(le, b"SEND FAIL", true),
+                |(data, tag): (&[u8], &[u8])| {
+                    (
+                        DigestResult::Response(Err(InternalError::Error)),
+                        data.len() + tag.len(),
+                    )
+                },
+            ),
+        ))(buf)
+    }
+
+    /// Matches a single line ending in an inline result code, the equivalent
+    /// of regex: "(\r\n)?(.*) (OK|ERROR)\r\n"
+    pub fn inline_result_response(buf: &[u8]) -> IResult<&[u8], (DigestResult<'_>, usize)> {
+        inline_result_response_with(buf, b"\r\n")
+    }
+
+    /// Like [`inline_result_response`], for lines terminated by `le` rather
+    /// than `\r\n`.
+    pub fn inline_result_response_with<'a>(
+        buf: &'a [u8],
+        le: &'static [u8],
+    ) -> IResult<&'a [u8], (DigestResult<'a>, usize)> {
+        let (rest, _) = opt(nom::bytes::complete::tag::<_, _, nom::error::Error<_>>(le))(buf)?;
+        let (rest, (line, _)) = take_until_including::<_, _, nom::error::Error<_>>(le)(rest)?;
+        let len = buf.len() - rest.len();
+
+        if let Some(data) = line.strip_suffix(b" OK") {
+            Ok((rest, (DigestResult::Response(Ok(data.trim_ascii())), len)))
+        } else if line.ends_with(b" ERROR") {
+            Ok((
+                rest,
+                (DigestResult::Response(Err(InternalError::Error)), len),
+            ))
+        } else {
+            Err(nom::Err::Error(nom::error::Error::new(
+                buf,
+                nom::error::ErrorKind::Tag,
+            )))
+        }
+    }
+
     /// Matches a full AT echo. Eg `AT+USORD=3,16\r\n`
     pub fn echo(buf: &[u8]) -> IResult<&[u8], &[u8]> {
-        if buf.len() < 2 {
+        echo_with(buf, b"\r\n")
+    }
+
+    /// Like [`echo`], for lines terminated by `le` rather than `\r\n`.
+    pub fn echo_with<'a>(buf: &'a [u8], le: &'static [u8]) -> IResult<&'a [u8], &'a [u8]> {
+        if buf.len() < le.len() {
             return Ok((buf, &[]));
         }
 
-        recognize(nom::bytes::complete::take_until("\r\n"))(buf)
+        recognize(nom::bytes::complete::take_until(le))(buf)
     }
 
     pub fn take_until_including<T, Input, Error: ParseError<Input>>(
@@ -375,19 +1117,18 @@ pub mod parser {
         }
     }
 
-    /// Matches the equivalent of regex: "{token}\s*(\d+)\r\n"
-    fn numeric_error<'a, T, Error: ParseError<&'a [u8]>>(
-        token: T,
+    /// Matches the equivalent of regex: "\r\n{token}\s*(\d+)\r\n"
+    fn numeric_error<'a, Error: ParseError<&'a [u8]>>(
+        le: &'static [u8],
+        token: &'static [u8],
     ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (u16, usize), Error>
     where
-        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
-        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
         nom::Err<Error>: From<nom::Err<nom::error::Error<&'a [u8]>>>,
     {
         move |i| {
             let (i, (prefix_data, (error_code, error_code_len), le)) = tuple((
                 recognize(tuple((
-                    take_until_including(token.clone()),
+                    take_until_line(le, token, false),
                     complete::multispace0,
                 ))),
                 map_res(complete::digit1, |digits| {
@@ -395,7 +1136,7 @@ pub mod parser {
                         .map_err(drop)
                         .map(|i| (i, digits.len()))
                 }),
-                complete::line_ending,
+                line_end(le),
             ))(i)?;
 
             Ok((
@@ -405,20 +1146,17 @@ pub mod parser {
         }
     }
 
-    /// Matches the equivalent of regex: "{token}\s*([^\n\r]+)\r\n"
+    /// Matches the equivalent of regex: "\r\n{token}\s*([^\n\r]+)\r\n"
     #[cfg(feature = "string_errors")]
-    fn string_error<'a, T, Error: ParseError<&'a [u8]>>(
-        token: T,
-    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], usize), Error>
-    where
-        &'a [u8]: nom::Compare<T> + nom::FindSubstring<T>,
-        T: nom::InputLength + Clone + nom::InputTake + nom::InputIter,
-    {
+    fn string_error<'a, Error: ParseError<&'a [u8]>>(
+        le: &'static [u8],
+        token: &'static [u8],
+    ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (&'a [u8], usize), Error> {
         move |i| {
             let (i, (prefix_data, _, error_msg)) = tuple((
-                recognize(take_until_including(token.clone())),
-                nom::combinator::not(tag("\r")),
-                recognize(take_until_including("\r\n")),
+                recognize(take_until_line(le, token, false)),
+                nom::combinator::not(tag(&le[..1])),
+                recognize(take_until_including(le)),
             ))(i)?;
 
             Ok((
@@ -430,11 +1168,12 @@ pub mod parser {
 
     /// Matches the equivalent of regex: "\r\n(ERROR)|(COMMAND NOT SUPPORT)\r\n"
     fn generic_error<'a, Error: ParseError<&'a [u8]>>(
+        le: &'static [u8],
     ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], usize, Error> {
-        move |i: &[u8]| {
+        move |i: &'a [u8]| {
             let (i, (data, tag)) = alt((
-                take_until_including("\r\nERROR\r\n"),
-                take_until_including("\r\nCOMMAND NOT SUPPORT\r\n"),
+                take_until_line(le, b"ERROR", true),
+                take_until_line(le, b"COMMAND NOT SUPPORT", true),
             ))(i)?;
 
             Ok((i, data.len() + tag.len()))
@@ -443,27 +1182,28 @@ pub mod parser {
 
     /// Matches the equivalent of regex: "\r\n(NO CARRIER)|(BUSY)|(NO ANSWER)|(NO DIALTONE)\r\n"
     fn connection_error<'a, Error: ParseError<&'a [u8]>>(
+        le: &'static [u8],
     ) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], (ConnectionError, usize), Error> {
-        move |i: &[u8]| {
+        move |i: &'a [u8]| {
             alt((
                 map(
-                    take_until_including("\r\nNO CARRIER\r\n"),
+                    take_until_line(le, b"NO CARRIER", true),
                     |(data, tag): (&[u8], &[u8])| {
                         (ConnectionError::NoCarrier, data.len() + tag.len())
                     },
                 ),
                 map(
-                    take_until_including("\r\nBUSY\r\n"),
+                    take_until_line(le, b"BUSY", true),
                     |(data, tag): (&[u8], &[u8])| (ConnectionError::Busy, data.len() + tag.len()),
                 ),
                 map(
-                    take_until_including("\r\nNO ANSWER\r\n"),
+                    take_until_line(le, b"NO ANSWER", true),
                     |(data, tag): (&[u8], &[u8])| {
                         (ConnectionError::NoAnswer, data.len() + tag.len())
                     },
                 ),
                 map(
-                    take_until_including("\r\nNO DIALTONE\r\n"),
+                    take_until_line(le, b"NO DIALTONE", true),
                     |(data, tag): (&[u8], &[u8])| {
                         (ConnectionError::NoDialtone, data.len() + tag.len())
                     },
@@ -479,11 +1219,56 @@ pub mod parser {
         }
     }
 }
+
+/// Fuzzing target, driving an [`AtDigester`] over arbitrary input in the same
+/// way as the ingress does, asserting that the digester never reports to have
+/// consumed more bytes than available.
+#[cfg(feature = "fuzz")]
+pub fn fuzz_digest(bytes: &[u8]) {
+    const FUZZ_BUF_LEN: usize = 256;
+    const FUZZ_CHUNK_LEN: usize = 64;
+
+    enum FuzzUrcParser {}
+
+    impl Parser for FuzzUrcParser {
+        fn parse(buf: &[u8]) -> Result<(&[u8], usize), ParseError> {
+            let (_, r) = nom::branch::alt((
+                parser::urc_helper("+UUSORD"),
+                parser::urc_helper("+CIEV"),
+                parser::urc_helper("+CREG"),
+            ))(buf)?;
+
+            Ok(r)
+        }
+    }
+
+    let mut digester = AtDigester::<FuzzUrcParser>::new();
+    let mut buf = heapless::Vec::<u8, FUZZ_BUF_LEN>::new();
+
+    for chunk in bytes.chunks(FUZZ_CHUNK_LEN) {
+        if buf.extend_from_slice(chunk).is_err() {
+            // Recover from a full buffer the same way the ingress does
+            buf.clear();
+            buf.extend_from_slice(chunk).unwrap();
+        }
+
+        loop {
+            let (_, consumed) = digester.digest(&buf);
+            assert!(consumed <= buf.len());
+            if consumed == 0 {
+                break;
+            }
+            buf.rotate_left(consumed);
+            buf.truncate(buf.len() - consumed);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use nom::{branch, bytes, character, combinator, sequence};
 
-    use super::parser::{echo, urc_helper};
+    use super::parser::{echo, urc_helper, urc_helper_with};
     use super::*;
     #[cfg(feature = "string_errors")]
     use crate::error::{CmsError, ConnectionError};
@@ -495,7 +1280,17 @@ mod test {
 
     impl Parser for UrcTestParser {
         fn parse(buf: &[u8]) -> Result<(&[u8], usize), ParseError> {
-            let (_, r) = nom::branch::alt((urc_helper("+UUSORD"), urc_helper("+CIEV")))(buf)?;
+            Self::parse_with_terminator(buf, b"\r\n")
+        }
+
+        fn parse_with_terminator<'a>(
+            buf: &'a [u8],
+            le: &'static [u8],
+        ) -> Result<(&'a [u8], usize), ParseError> {
+            let (_, r) = nom::branch::alt((
+                urc_helper_with("+UUSORD", le),
+                urc_helper_with("+CIEV", le),
+            ))(buf)?;
 
             Ok(r)
         }
@@ -709,6 +1504,111 @@ mod test {
         }
     }
 
+    #[test]
+    fn mm_ok_lf_terminated() {
+        let tests: Vec<(&[u8], DigestResult, usize)> = vec![
+            (b"\nOK\n", DigestResult::Response(Ok(b"")), 4),
+            (
+                b"\nOK\n\n+CMTI: \"ME\",1\n",
+                DigestResult::Response(Ok(b"")),
+                4,
+            ),
+            (
+                b"\nOK\n\n+CIEV: 7,1\n\n+CRING: VOICE\n\n+CLIP: \"+0123456789\",145,,,,0\n",
+                DigestResult::Response(Ok(b"")),
+                4,
+            ),
+            (
+                b"\n+CIEV: 7,1\n\n+CRING: VOICE\n\n+CLIP: \"+0123456789\",145,,,,0\n",
+                DigestResult::Urc(b"+CIEV: 7,1"),
+                12,
+            ),
+            (b"\nUNKNOWN COMMAND\n", DigestResult::None, 0),
+        ];
+
+        let mut digester = AtDigester::<UrcTestParser>::new().with_line_terminator(b"\n");
+        let mut buf = heapless::Vec::<u8, TEST_RX_BUF_LEN>::new();
+
+        for (response, expected_result, swallowed_bytes) in tests {
+            buf.clear();
+
+            buf.extend_from_slice(response).unwrap();
+            let (res, bytes) = digester.digest(&buf);
+            assert_eq!((res, bytes), (expected_result, swallowed_bytes));
+
+            buf.rotate_left(bytes);
+            buf.truncate(buf.len() - bytes);
+        }
+    }
+
+    #[test]
+    fn lf_terminated_responses() {
+        let mut digester = AtDigester::<UrcTestParser>::new().with_line_terminator(b"\n");
+
+        // Echo, response data and result code
+        assert_eq!(
+            (DigestResult::Response(Ok(b"+CMD: 1")), 19),
+            digester.digest(b"AT+CMD\n+CMD: 1\n\nOK\n")
+        );
+        assert_eq!(
+            (DigestResult::Response(Err(InternalError::Error)), 7),
+            digester.digest(b"\nERROR\n")
+        );
+        assert_eq!(
+            (
+                DigestResult::Response(Err(InternalError::CmeError(CmeError::Unknown))),
+                17
+            ),
+            digester.digest(b"\n+CME ERROR: 100\n")
+        );
+        assert_eq!(
+            (
+                DigestResult::Urc(b"+UUSORD: 3,16,\"123.34.23.43\",1234"),
+                35
+            ),
+            digester.digest(b"\n+UUSORD: 3,16,\"123.34.23.43\",1234\n")
+        );
+
+        // Incomplete frames are left in the buffer
+        assert_eq!((DigestResult::None, 0), digester.digest(b"\nOK"));
+        assert_eq!((DigestResult::None, 0), digester.digest(b"\n+CIEV: 7,1"));
+
+        // URCs are not limited in length
+        let mut urc = b"\n+UUSORD: 3,200,\"".to_vec();
+        urc.extend_from_slice(&[b'a'; 200]);
+        urc.extend_from_slice(b"\"\n");
+        assert_eq!(
+            (DigestResult::Urc(&urc[1..urc.len() - 1]), urc.len()),
+            digester.digest(&urc)
+        );
+    }
+
+    #[test]
+    fn lf_terminated_optional_matchers() {
+        let mut digester = AtDigester::<UrcTestParser>::new()
+            .with_line_terminator(b"\n")
+            .with_send_result_tokens()
+            .with_inline_result_codes()
+            .with_urc_preambles(&[b"^SID:"]);
+
+        assert_eq!(
+            (DigestResult::Response(Ok(b"")), 9),
+            digester.digest(b"\nSEND OK\n")
+        );
+        assert_eq!(
+            (DigestResult::Response(Err(InternalError::Error)), 11),
+            digester.digest(b"\nSEND FAIL\n")
+        );
+        assert_eq!(
+            (DigestResult::Response(Ok(b"+CMD: 5")), 12),
+            digester.digest(b"\n+CMD: 5 OK\n")
+        );
+        assert_eq!(
+            (DigestResult::Urc(b"+CIEV: 7,1"), 20),
+            digester.digest(b"\n^SID: 3\n+CIEV: 7,1\n")
+        );
+    }
+
     #[test]
     fn space_removal() {
         // Space can happen after a "\r\n> " prompt,
@@ -719,6 +1619,22 @@ mod test {
         assert_eq!((DigestResult::None, 1), digester.digest(b" "));
     }
 
+    #[test]
+    fn prompt_with_trailing_space() {
+        let mut digester = AtDigester::<UrcTestParser>::new();
+
+        assert_eq!((DigestResult::Prompt(b'>'), 4), digester.digest(b"\r\n> "));
+
+        let mut digester = AtDigester::<UrcTestParser>::new().with_custom_prompt(|buf| {
+            match buf.iter().position(|&c| c == b'$') {
+                Some(pos) => Ok((b'$', pos + 1)),
+                None => Err(ParseError::NoMatch),
+            }
+        });
+
+        assert_eq!((DigestResult::Prompt(b'$'), 4), digester.digest(b"\r\n$ "));
+    }
+
     #[test]
     fn echo_test() {
         let (r, e) = echo(b"AT\r\n").unwrap();
@@ -1144,6 +2060,449 @@ mod test {
         assert!(buf.is_empty());
     }
 
+    #[test]
+    fn garbage_observer() {
+        static OBSERVED: std::sync::Mutex<std::vec::Vec<u8>> =
+            std::sync::Mutex::new(std::vec::Vec::new());
+
+        let mut digester = AtDigester::<UrcTestParser>::new().with_garbage_observer(|garbage| {
+            OBSERVED.lock().unwrap().extend_from_slice(garbage);
+        });
+        let mut buf = heapless::Vec::<u8, TEST_RX_BUF_LEN>::new();
+
+        buf.extend_from_slice(b"\r\nGARBAGE\r\n+CIEV: 1,1\r\n")
+            .unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!((res, bytes), (DigestResult::Urc(b"+CIEV: 1,1"), 23));
+        assert_eq!(OBSERVED.lock().unwrap().as_slice(), b"GARBAGE");
+    }
+
+    #[test]
+    fn urc_only_len() {
+        let buf = b"\r\nGARBAGE\r\n+CIEV: 1,1\r\n";
+
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        assert_eq!(digester.digest(buf), (DigestResult::Urc(b"+CIEV: 1,1"), 23));
+        assert_eq!(urc_len::<UrcTestParser>(buf), Some(14));
+
+        assert_eq!(urc_len::<UrcTestParser>(b"\r\nGARBAGE\r\n"), None);
+    }
+
+    #[test]
+    fn connect_with_rate() {
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        let mut buf = heapless::Vec::<u8, TEST_RX_BUF_LEN>::new();
+
+        buf.extend_from_slice(b"ATD*99#\r\r\nCONNECT 96").unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!((res, bytes), (DigestResult::None, 8));
+        buf.rotate_left(bytes);
+        buf.truncate(buf.len() - bytes);
+
+        buf.extend_from_slice(b"00\r\n").unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!(
+            (res, bytes),
+            (DigestResult::Response(Ok(b"CONNECT 9600")), 16)
+        );
+        buf.rotate_left(bytes);
+        buf.truncate(buf.len() - bytes);
+        assert!(buf.is_empty());
+
+        let rate: u32 = serde_at::from_slice(&b"CONNECT 9600"[b"CONNECT ".len()..]).unwrap();
+        assert_eq!(rate, 9600);
+
+        buf.extend_from_slice(b"ATD*99#\r\r\nCONNECT\r\n").unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!((res, bytes), (DigestResult::Response(Ok(b"")), 19));
+        buf.clear();
+
+        // Information lines preceding the rate are kept, as for `CONNECT`
+        buf.extend_from_slice(b"\r\n+CGDATA: 1\r\n\r\nCONNECT 9600\r\n")
+            .unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!(
+            (res, bytes),
+            (
+                DigestResult::Response(Ok(b"+CGDATA: 1\r\n\r\nCONNECT 9600")),
+                30
+            )
+        );
+        buf.clear();
+
+        buf.extend_from_slice(b"\r\n+CGDATA: 1\r\n\r\nCONNECT\r\n")
+            .unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!(
+            (res, bytes),
+            (DigestResult::Response(Ok(b"+CGDATA: 1")), 25)
+        );
+    }
+
+    #[test]
+    fn digest_complete_waits_for_full_frame() {
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        let mut buf = heapless::Vec::<u8, TEST_RX_BUF_LEN>::new();
+
+        buf.extend_from_slice(b"AT+CSQ\r\r\n+CSQ: 12").unwrap();
+        assert_eq!(digester.digest_complete(&buf), None);
+
+        buf.extend_from_slice(b",99\r\n\r\nOK\r\n").unwrap();
+        let (res, bytes) = digester.digest_complete(&buf).unwrap();
+        assert_eq!(
+            (res, bytes),
+            (DigestResult::Response(Ok(b"+CSQ: 12,99")), 28)
+        );
+        buf.rotate_left(bytes);
+        buf.truncate(buf.len() - bytes);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn urc_without_space_after_colon() {
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        let mut buf = heapless::Vec::<u8, TEST_RX_BUF_LEN>::new();
+
+        buf.extend_from_slice(b"\r\n+CIEV:7,1\r\n").unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!((res, bytes), (DigestResult::Urc(b"+CIEV:7,1"), 13));
+    }
+
+    #[test]
+    fn reboot_indicator() {
+        use core::sync::atomic::{AtomicBool, Ordering};
+
+        static REBOOTED: AtomicBool = AtomicBool::new(false);
+
+        let mut digester = AtDigester::<UrcTestParser>::new().with_reboot_indicators(
+            &[b"RDY", b"*ATREADY"],
+            |_| {
+                REBOOTED.store(true, Ordering::Relaxed);
+            },
+        );
+        let mut buf = heapless::Vec::<u8, TEST_RX_BUF_LEN>::new();
+
+        buf.extend_from_slice(b"\r\n+CIEV: 1,1\r\n").unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!((res, bytes), (DigestResult::Urc(b"+CIEV: 1,1"), 14));
+        assert!(!REBOOTED.load(Ordering::Relaxed));
+
+        buf.clear();
+        buf.extend_from_slice(b"\r\nRDY\r\n").unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!((res, bytes), (DigestResult::Urc(b"RDY"), 7));
+        assert!(REBOOTED.load(Ordering::Relaxed));
+
+        let mut digester = digester.with_reboot_failing_pending();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!(
+            (res, bytes),
+            (DigestResult::Response(Err(InternalError::Rebooted)), 7)
+        );
+    }
+
+    #[test]
+    fn line_terminator_without_echo() {
+        let mut digester = AtDigester::<UrcTestParser>::new().with_line_terminator(b"\n");
+        digester.set_echo_stripping(false);
+
+        assert_eq!((DigestResult::None, 0), digester.digest(b"+CMD: 1\nO"));
+        assert_eq!(
+            (DigestResult::Response(Ok(b"+CMD: 1\n+CMD: 2")), 19),
+            digester.digest(b"+CMD: 1\n+CMD: 2\nOK\n")
+        );
+        assert_eq!(
+            (DigestResult::Response(Ok(b"")), 4),
+            digester.digest(b"\nOK\n")
+        );
+        assert_eq!(
+            (DigestResult::Response(Err(InternalError::Error)), 7),
+            digester.digest(b"\nERROR\n")
+        );
+        assert_eq!(
+            (DigestResult::Urc(b"+CIEV: 7,1"), 12),
+            digester.digest(b"\n+CIEV: 7,1\n")
+        );
+
+        // `\r\n` does not terminate result codes
+        assert_eq!((DigestResult::None, 0), digester.digest(b"\r\nOK\r"));
+
+        // Vendor specific terminator
+        let mut digester = AtDigester::<UrcTestParser>::new().with_line_terminator(b"\x1e");
+        digester.set_echo_stripping(false);
+        assert_eq!(
+            (DigestResult::Response(Ok(b"+CMD: 1")), 11),
+            digester.digest(b"+CMD: 1\x1eOK\x1e")
+        );
+    }
+
+    #[test]
+    fn set_urc_parser() {
+        fn cgev_parser(buf: &[u8]) -> Result<(&[u8], usize), ParseError> {
+            let (_, r) = urc_helper("+CGEV")(buf)?;
+            Ok(r)
+        }
+
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        assert_eq!(
+            (DigestResult::Urc(b"+CIEV: 7,1"), 14),
+            digester.digest(b"\r\n+CIEV: 7,1\r\n")
+        );
+
+        digester.set_urc_parser(cgev_parser);
+        assert_eq!(
+            (DigestResult::Urc(b"+CGEV: NW DETACH"), 20),
+            digester.digest(b"\r\n+CGEV: NW DETACH\r\n")
+        );
+
+        let mut digester = AtDigester::<UrcTestParser>::new().with_urc_parser(cgev_parser);
+        assert_eq!(
+            (DigestResult::Urc(b"+CGEV: ME PDN ACT 1"), 23),
+            digester.digest(b"\r\n+CGEV: ME PDN ACT 1\r\n")
+        );
+    }
+
+    #[test]
+    fn urc_preamble() {
+        let mut digester = AtDigester::<UrcTestParser>::new().with_urc_preambles(&[b"^SID"]);
+
+        assert_eq!(
+            (DigestResult::Urc(b"+CIEV: 7,1"), 21),
+            digester.digest(b"^SID: 3\r\n+CIEV: 7,1\r\n")
+        );
+
+        // The preamble is not mistaken for a response when echo is disabled
+        digester.set_echo_stripping(false);
+        assert_eq!(
+            (DigestResult::Urc(b"+CIEV: 7,1"), 21),
+            digester.digest(b"^SID: 3\r\n+CIEV: 7,1\r\n")
+        );
+        assert_eq!(
+            (DigestResult::Urc(b"+CIEV: 7,1"), 23),
+            digester.digest(b"\r\n^SID: 3\r\n+CIEV: 7,1\r\n")
+        );
+
+        // Incomplete preamble line
+        assert_eq!((DigestResult::None, 0), digester.digest(b"^SID: 3"));
+    }
+
+    #[test]
+    fn urc_stem() {
+        let parse = |buf: &'static [u8]| {
+            parser::urc_helper_stem::<_, nom::error::Error<&[u8]>>("+CEREG")(buf)
+        };
+
+        assert_eq!(
+            parse(b"\r\n+CEREG: 1\r\n").unwrap().1,
+            (&b"+CEREG: 1"[..], 13)
+        );
+        assert_eq!(
+            parse(b"\r\n+CEREG2: 1,5\r\n").unwrap().1,
+            (&b"+CEREG2: 1,5"[..], 16)
+        );
+        assert_eq!(parse(b"\r\n+CEREG2\r\n").unwrap().1, (&b"+CEREG2"[..], 11));
+        assert!(matches!(
+            parse(b"\r\n+CEREG2"),
+            Err(nom::Err::Incomplete(_))
+        ));
+        assert!(matches!(
+            parse(b"\r\n+CEREGX: 1\r\n"),
+            Err(nom::Err::Error(_))
+        ));
+
+        let parse = |buf: &'static [u8]| {
+            parser::urc_helper_stem_with::<_, nom::error::Error<&[u8]>>("+CEREG", b"\n")(buf)
+        };
+
+        assert_eq!(
+            parse(b"\n+CEREG2: 1,5\n").unwrap().1,
+            (&b"+CEREG2: 1,5"[..], 14)
+        );
+        assert_eq!(parse(b"\n+CEREG\n").unwrap().1, (&b"+CEREG"[..], 8));
+        assert!(matches!(parse(b"\n+CEREG2"), Err(nom::Err::Incomplete(_))));
+    }
+
+    #[test]
+    fn length_delimited_urc() {
+        let parse = |buf: &'static [u8]| {
+            parser::length_delimited_urc_helper::<_, nom::error::Error<&[u8]>>("+QMTRECV")(buf)
+        };
+
+        assert_eq!(
+            parse(b"\r\n+QMTRECV: 0,0,\"t\",4,a\r\nb\r\n").unwrap().1,
+            (&b"+QMTRECV: 0,0,\"t\",4,a\r\nb"[..], 28)
+        );
+
+        let parse = |buf: &'static [u8]| {
+            parser::length_delimited_urc_helper_with::<_, nom::error::Error<&[u8]>>(
+                "+QMTRECV", b"\n",
+            )(buf)
+        };
+
+        assert_eq!(
+            parse(b"\n+QMTRECV: 0,0,\"t\",3,a\nb\n").unwrap().1,
+            (&b"+QMTRECV: 0,0,\"t\",3,a\nb"[..], 25)
+        );
+        assert!(matches!(
+            parse(b"\n+QMTRECV: 0,0,\"t\",3,a\n"),
+            Err(nom::Err::Incomplete(_))
+        ));
+    }
+
+    #[test]
+    fn abort_token() {
+        let mut digester = AtDigester::<UrcTestParser>::new().with_abort_token(b"ABORTED");
+
+        assert_eq!((DigestResult::None, 0), digester.digest(b"\r\nABORTED"));
+        assert_eq!(
+            (DigestResult::Response(Err(InternalError::Aborted)), 11),
+            digester.digest(b"\r\nABORTED\r\n")
+        );
+
+        // Without a registered token, the line is not recognized
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        assert_ne!(
+            DigestResult::Response(Err(InternalError::Aborted)),
+            digester.digest(b"\r\nABORTED\r\n").0
+        );
+    }
+
+    #[test]
+    fn set_custom_success() {
+        let mut digester = AtDigester::<UrcTestParser>::new();
+
+        assert_eq!((DigestResult::None, 0), digester.digest(b"\r\nDONE\r\n"));
+
+        digester.set_custom_success(|buf| {
+            let (_, (data, tag)) =
+                parser::take_until_including::<_, _, nom::error::Error<_>>("\r\nDONE\r\n")(buf)
+                    .map_err(|_| ParseError::NoMatch)?;
+            Ok((data.trim_ascii(), data.len() + tag.len()))
+        });
+        assert_eq!(
+            (DigestResult::Response(Ok(b"")), 8),
+            digester.digest(b"\r\nDONE\r\n")
+        );
+
+        digester.set_custom_success(|_| Err(ParseError::NoMatch));
+        assert_eq!((DigestResult::None, 0), digester.digest(b"\r\nDONE\r\n"));
+    }
+
+    #[test]
+    fn send_result_tokens() {
+        let mut digester = AtDigester::<UrcTestParser>::new().with_send_result_tokens();
+
+        assert_eq!((DigestResult::None, 0), digester.digest(b"\r\nSEND OK"));
+        assert_eq!(
+            (DigestResult::Response(Ok(b"")), 11),
+            digester.digest(b"\r\nSEND OK\r\n")
+        );
+        assert_eq!(
+            (DigestResult::Response(Ok(b"Recv 5 bytes")), 27),
+            digester.digest(b"\r\nRecv 5 bytes\r\n\r\nSEND OK\r\n")
+        );
+        assert_eq!(
+            (DigestResult::Response(Err(InternalError::Error)), 13),
+            digester.digest(b"\r\nSEND FAIL\r\n")
+        );
+
+        // Without enabling the tokens, the lines are not recognized
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        assert_eq!((DigestResult::None, 0), digester.digest(b"\r\nSEND OK\r\n"));
+    }
+
+    #[test]
+    fn inline_result_codes() {
+        let mut digester = AtDigester::<UrcTestParser>::new().with_inline_result_codes();
+
+        assert_eq!(
+            (DigestResult::Response(Ok(b"+CMD: 5")), 21),
+            digester.digest(b"AT+CMD\r\r\n+CMD: 5 OK\r\n")
+        );
+        assert_eq!(
+            (DigestResult::Response(Err(InternalError::Error)), 17),
+            digester.digest(b"\r\n+CMD: 5 ERROR\r\n")
+        );
+
+        // Regular result codes are still recognized
+        assert_eq!(
+            (DigestResult::Response(Ok(b"+CMD: 5")), 17),
+            digester.digest(b"\r\n+CMD: 5\r\n\r\nOK\r\n")
+        );
+
+        digester.set_echo_stripping(false);
+        assert_eq!(
+            (DigestResult::Response(Ok(b"+CMD: 5")), 12),
+            digester.digest(b"+CMD: 5 OK\r\n")
+        );
+
+        // Without enabling, the line is not recognized
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        digester.set_echo_stripping(false);
+        assert_eq!((DigestResult::None, 0), digester.digest(b"+CMD: 5 OK\r\n"));
+    }
+
+    #[test]
+    fn max_echo_len() {
+        // Echo running straight into the response, without a `\r\n`
+        let buf = b"AT+CMD\r+CMD: 1\r\n\r\nOK\r\n";
+
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        assert_eq!((DigestResult::Response(Ok(b"")), 22), digester.digest(buf));
+
+        let mut digester = AtDigester::<UrcTestParser>::new().with_max_echo_len(7);
+        assert_eq!(
+            (DigestResult::Response(Ok(b"+CMD: 1")), 22),
+            digester.digest(buf)
+        );
+    }
+
+    #[test]
+    fn max_response_len() {
+        let mut digester = AtDigester::<UrcTestParser>::new().with_max_response_len(8);
+        let mut buf = heapless::Vec::<u8, TEST_RX_BUF_LEN>::new();
+
+        buf.extend_from_slice(b"AT+CMD\r\r\n+CMD: 123456\r\n\r\nOK\r\n")
+            .unwrap();
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!(
+            (res, bytes),
+            (
+                DigestResult::Response(Err(InternalError::InvalidResponse)),
+                29
+            )
+        );
+
+        let mut digester = digester.with_max_response_len(12);
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!(
+            (res, bytes),
+            (DigestResult::Response(Ok(b"+CMD: 123456")), 29)
+        );
+    }
+
+    #[test]
+    fn no_echo_keeps_leading_line() {
+        let mut digester = AtDigester::<UrcTestParser>::new();
+        let mut buf = heapless::Vec::<u8, TEST_RX_BUF_LEN>::new();
+
+        buf.extend_from_slice(b"+CSQ: 12,99\r\n\r\nOK\r\n").unwrap();
+
+        // With echo stripping, the leading line is mistaken for echo
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!((res, bytes), (DigestResult::Response(Ok(b"")), 19));
+
+        digester.set_echo_stripping(false);
+        let (res, bytes) = digester.digest(&buf);
+        assert_eq!(
+            (res, bytes),
+            (DigestResult::Response(Ok(b"+CSQ: 12,99")), 19)
+        );
+        buf.rotate_left(bytes);
+        buf.truncate(buf.len() - bytes);
+        assert!(buf.is_empty());
+    }
+
     // Regression test for #87
     #[test]
     fn cpin_parsing() {
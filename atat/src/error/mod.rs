@@ -25,6 +25,9 @@ pub enum InternalError<'a> {
     /// Command was aborted
     #[error("Command was aborted")]
     Aborted,
+    /// Module rebooted while waiting for a response
+    #[error("Module rebooted while waiting for a response")]
+    Rebooted,
     /// Failed to parse received response
     #[error("Failed to parse received response")]
     Parse,
@@ -57,6 +60,7 @@ impl<'a> defmt::Format for InternalError<'a> {
             InternalError::Timeout => defmt::write!(f, "InternalError::Timeout"),
             InternalError::InvalidResponse => defmt::write!(f, "InternalError::InvalidResponse"),
             InternalError::Aborted => defmt::write!(f, "InternalError::Aborted"),
+            InternalError::Rebooted => defmt::write!(f, "InternalError::Rebooted"),
             InternalError::Parse => defmt::write!(f, "InternalError::Parse"),
             InternalError::Capacity => defmt::write!(f, "InternalError::Capacity"),
             InternalError::Error => defmt::write!(f, "InternalError::Error"),
@@ -91,12 +95,18 @@ pub enum Error {
     /// Command was aborted
     #[error("Command was aborted")]
     Aborted,
+    /// Module rebooted while waiting for a response
+    #[error("Module rebooted while waiting for a response")]
+    Rebooted,
     /// Failed to parse received response
     #[error("Failed to parse received response")]
     Parse,
     /// Received response exceeded capacity
     #[error("Received response exceeded capacity")]
     Capacity,
+    /// Command exceeded the capacity of the transmit buffer
+    #[error("Command exceeded the capacity of the transmit buffer")]
+    Overflow,
     /// Generic error response without any error message
     #[error("Generic error response")]
     Error,
@@ -123,6 +133,7 @@ impl embedded_io::Error for Error {
             Self::Timeout => embedded_io::ErrorKind::TimedOut,
             Self::InvalidResponse => embedded_io::ErrorKind::InvalidData,
             Self::Aborted => embedded_io::ErrorKind::ConnectionAborted,
+            Self::Rebooted => embedded_io::ErrorKind::ConnectionReset,
             Self::Parse => embedded_io::ErrorKind::InvalidData,
             Self::ConnectionError(e) => match e {
                 ConnectionError::Unknown => embedded_io::ErrorKind::NotConnected,
@@ -138,6 +149,12 @@ impl embedded_io::Error for Error {
     }
 }
 
+impl From<Error> for embedded_io::ErrorKind {
+    fn from(e: Error) -> Self {
+        embedded_io::Error::kind(&e)
+    }
+}
+
 impl<'a> From<InternalError<'a>> for Error {
     fn from(ie: InternalError) -> Self {
         match ie {
@@ -146,6 +163,7 @@ impl<'a> From<InternalError<'a>> for Error {
             InternalError::Timeout => Self::Timeout,
             InternalError::InvalidResponse => Self::InvalidResponse,
             InternalError::Aborted => Self::Aborted,
+            InternalError::Rebooted => Self::Rebooted,
             InternalError::Parse => Self::Parse,
             InternalError::Capacity => Self::Capacity,
             InternalError::Error => Self::Error,
@@ -162,6 +180,27 @@ impl<'a> From<InternalError<'a>> for Error {
     }
 }
 
+impl Error {
+    /// Convert an [`InternalError`] into an [`Error`], copying the bytes of an
+    /// [`InternalError::Custom`] error message into `buf`.
+    ///
+    /// This allows retaining the error message without the
+    /// `custom-error-messages` feature. Returns the error along with the number
+    /// of bytes copied into `buf`, truncated to the length of `buf`.
+    pub fn from_internal_with_buf(ie: InternalError<'_>, buf: &mut [u8]) -> (Self, usize) {
+        let len = match ie {
+            InternalError::Custom(e) => {
+                let len = core::cmp::min(e.len(), buf.len());
+                buf[..len].copy_from_slice(&e[..len]);
+                len
+            }
+            _ => 0,
+        };
+
+        (ie.into(), len)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,7 +213,9 @@ mod tests {
         assert_eq!(Error::Timeout.kind(), ErrorKind::TimedOut);
         assert_eq!(Error::InvalidResponse.kind(), ErrorKind::InvalidData);
         assert_eq!(Error::Aborted.kind(), ErrorKind::ConnectionAborted);
+        assert_eq!(Error::Rebooted.kind(), ErrorKind::ConnectionReset);
         assert_eq!(Error::Parse.kind(), ErrorKind::InvalidData);
+        assert_eq!(Error::Overflow.kind(), ErrorKind::Other);
         assert_eq!(Error::Error.kind(), ErrorKind::Other);
         assert_eq!(
             Error::ConnectionError(ConnectionError::Unknown).kind(),
@@ -198,4 +239,49 @@ mod tests {
         );
         assert_eq!(Error::Custom.kind(), ErrorKind::Other);
     }
+
+    #[test]
+    fn test_into_error_kind() {
+        let cases = [
+            (Error::Read, ErrorKind::Other),
+            (Error::Write, ErrorKind::Other),
+            (Error::Timeout, ErrorKind::TimedOut),
+            (Error::InvalidResponse, ErrorKind::InvalidData),
+            (Error::Aborted, ErrorKind::ConnectionAborted),
+            (Error::Rebooted, ErrorKind::ConnectionReset),
+            (Error::Parse, ErrorKind::InvalidData),
+            (Error::Capacity, ErrorKind::Other),
+            (Error::Overflow, ErrorKind::Other),
+            (Error::Error, ErrorKind::Other),
+            (Error::CmeError(CmeError::SimBusy), ErrorKind::Other),
+            (Error::CmsError(CmsError::MeFailure), ErrorKind::Other),
+            (
+                Error::ConnectionError(ConnectionError::NoCarrier),
+                ErrorKind::ConnectionReset,
+            ),
+            (Error::Custom, ErrorKind::Other),
+        ];
+
+        for (err, kind) in cases {
+            assert_eq!(ErrorKind::from(err), kind);
+        }
+    }
+
+    #[test]
+    fn test_from_internal_with_buf() {
+        let mut buf = [0; 32];
+
+        let ie = InternalError::Custom(b"+CUSTOM ERROR: 42");
+        let (err, len) = Error::from_internal_with_buf(ie.clone(), &mut buf);
+        assert_eq!(err, Error::from(ie));
+        assert_eq!(&buf[..len], b"+CUSTOM ERROR: 42");
+
+        let mut short_buf = [0; 7];
+        let (_, len) = Error::from_internal_with_buf(ie, &mut short_buf);
+        assert_eq!(len, 7);
+        assert_eq!(&short_buf, b"+CUSTOM");
+
+        let (err, len) = Error::from_internal_with_buf(InternalError::Timeout, &mut buf);
+        assert_eq!((err, len), (Error::Timeout, 0));
+    }
 }
@@ -1,5 +1,19 @@
 /// Wrapper for a byte-slice that formats it as a string if possible and as
 /// bytes otherwise.
+///
+/// Useful for logging raw modem data, which is usually, but not always, valid
+/// UTF-8. Implements `Debug`, and `defmt::Format` when the `defmt` feature is
+/// enabled.
+///
+/// Example:
+/// ```
+/// use atat::helpers::LossyStr;
+///
+/// assert_eq!(format!("{:?}", LossyStr(b"+CSQ: 12,99")), "\"+CSQ: 12,99\"");
+///
+/// // Invalid UTF-8 falls back to formatting the raw bytes
+/// assert_eq!(format!("{:?}", LossyStr(&[b'O', b'K', 0xFF])), "[79, 75, 255]");
+/// ```
 pub struct LossyStr<'a>(pub &'a [u8]);
 
 impl<'a> core::fmt::Debug for LossyStr<'a> {
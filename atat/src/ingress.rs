@@ -70,6 +70,26 @@ pub trait AtatIngress {
         }
     }
 
+    /// Read the bytes available from the provided serial in a single read,
+    /// and ingest them into the ingress from where they will be processed.
+    ///
+    /// Returns the number of bytes read. The bytes are ingested even if they
+    /// could not all be processed yet, eg. as the URC channel is full, in
+    /// which case they are processed as part of the next advance.
+    fn try_read_from<R: embedded_io::Read>(&mut self, serial: &mut R) -> Result<usize, R::Error> {
+        if self.write_buf().is_empty() {
+            warn!("Ingress buffer full, clearing");
+            self.clear();
+        }
+        let received = serial.read(self.write_buf())?;
+        if received > 0 {
+            if let Err(e) = self.try_advance(received) {
+                warn!("Failed to process ingested bytes: {:?}", e);
+            }
+        }
+        Ok(received)
+    }
+
     /// Read all bytes from the provided serial and ingest the read bytes into
     /// the ingress from where they will be processed
     async fn read_from<R: embedded_io_async::Read>(&mut self, mut serial: R) -> ! {
@@ -110,6 +130,7 @@ pub struct Ingress<
     buf: &'a mut [u8],
     pos: usize,
     res_slot: &'a ResponseSlot<RES_BUF_SIZE>,
+    urc_channel: &'a UrcChannel<Urc, URC_CAPACITY, URC_SUBSCRIBERS>,
     urc_publisher: UrcPublisher<'a, Urc, URC_CAPACITY, URC_SUBSCRIBERS>,
 }
 
@@ -133,9 +154,66 @@ impl<
             buf,
             pos: 0,
             res_slot,
-            urc_publisher: urc_channel.0.publisher().unwrap(),
+            urc_channel,
+            urc_publisher: urc_channel.channel.publisher().unwrap(),
         }
     }
+
+    /// Returns a mutable reference to the digester, eg. to reconfigure it
+    /// when switching the modem between data and command mode.
+    pub fn digester_mut(&mut self) -> &mut D {
+        &mut self.digester
+    }
+
+    /// Digest the buffered bytes, calling `f` with each digest result in turn,
+    /// until the buffer yields no more complete frames.
+    ///
+    /// Unlike [`AtatIngress::advance`], results are handed to `f` rather than
+    /// being dispatched to the response slot and URC channel. This can be used
+    /// to fully process any frames left buffered, eg. after
+    /// [`AtatIngress::try_advance`] failed with [`Error::UrcChannelFull`].
+    pub fn process<F: FnMut(DigestResult<'_>)>(&mut self, mut f: F) {
+        self.digester
+            .set_echo_stripping(self.res_slot.echo_expected());
+
+        while self.pos > 0 {
+            let (result, swallowed) = self.digester.digest(&self.buf[..self.pos]);
+            if swallowed == 0 {
+                break;
+            }
+
+            f(result);
+
+            self.buf.copy_within(swallowed..self.pos, 0);
+            self.pos -= swallowed;
+        }
+    }
+
+    /// Drop the oldest bytes of a full buffer in which no frame can be
+    /// completed, to make room for new data.
+    ///
+    /// Bytes are dropped up to the last line terminator of the digester,
+    /// which may start a frame, or entirely if there is none.
+    fn make_room(&mut self) {
+        if self.pos < self.buf.len() {
+            return;
+        }
+
+        let le = self.digester.line_terminator();
+        let dropped = self.buf[..self.pos]
+            .windows(le.len())
+            .rposition(|w| w == le)
+            .filter(|&i| i > 0)
+            .unwrap_or(self.pos);
+        warn!(
+            "Ingress buffer full, dropping {} bytes: {:?}",
+            dropped,
+            LossyStr(&self.buf[..dropped])
+        );
+        self.digester.discard(&self.buf[..dropped]);
+        self.buf.copy_within(dropped..self.pos, 0);
+        self.pos -= dropped;
+    }
 }
 
 impl<
@@ -155,6 +233,10 @@ impl<
         assert!(self.pos <= self.buf.len());
 
         while self.pos > 0 {
+            // Updated for every frame, as signaling a response restores the
+            // echo expected while idle
+            self.digester
+                .set_echo_stripping(self.res_slot.echo_expected());
             let swallowed = match self.digester.digest(&self.buf[..self.pos]) {
                 (DigestResult::None, swallowed) => {
                     if swallowed > 0 {
@@ -187,8 +269,8 @@ impl<
                             LossyStr(urc_line)
                         );
 
-                        self.urc_publisher
-                            .try_publish(urc)
+                        self.urc_channel
+                            .try_publish(&self.urc_publisher, urc, urc_line.len())
                             .map_err(|_| Error::UrcChannelFull)?;
                     } else {
                         error!("Parsing URC FAILED: {:?}", LossyStr(urc_line));
@@ -232,6 +314,7 @@ impl<
             self.pos -= swallowed;
         }
 
+        self.make_room();
         Ok(())
     }
 
@@ -240,6 +323,10 @@ impl<
         assert!(self.pos <= self.buf.len());
 
         while self.pos > 0 {
+            // Updated for every frame, as signaling a response restores the
+            // echo expected while idle
+            self.digester
+                .set_echo_stripping(self.res_slot.echo_expected());
             let swallowed = match self.digester.digest(&self.buf[..self.pos]) {
                 (DigestResult::None, swallowed) => {
                     if swallowed > 0 {
@@ -271,7 +358,10 @@ impl<
                             LossyStr(urc_line)
                         );
 
-                        if let Err(urc) = self.urc_publisher.try_publish(urc) {
+                        if let Err(urc) =
+                            self.urc_channel
+                                .try_publish(&self.urc_publisher, urc, urc_line.len())
+                        {
                             self.urc_publisher.publish(urc).await;
                         }
                     } else {
@@ -315,6 +405,8 @@ impl<
             self.buf.copy_within(swallowed..self.pos, 0);
             self.pos -= swallowed;
         }
+
+        self.make_room();
     }
 
     fn clear(&mut self) {
@@ -326,7 +418,8 @@ impl<
 mod tests {
     use crate::{
         self as atat, atat_derive::AtatUrc, digest::parser::take_until_including,
-        response_slot::ResponseSlot, AtDigester, Response, UrcChannel,
+        response_slot::ResponseSlot, AtDigester, Response, UrcChannel, UrcMessage,
+        UrcOverflowPolicy,
     };
     use embedded_io::ErrorType;
 
@@ -474,6 +567,265 @@ mod tests {
         assert_eq!(Response::default(), *response);
     }
 
+    #[test]
+    fn echo_stripping_restored_after_response() {
+        let res_slot = ResponseSlot::<100>::new();
+        let urc_channel = UrcChannel::<Urc, 10, 1>::new();
+        let mut buf = [0; 100];
+
+        let mut ingress: Ingress<_, Urc, 100, 10, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+        let mut sub = urc_channel.subscribe().unwrap();
+
+        // Response to a command that is not echoed, followed by the echo of
+        // another command
+        res_slot.set_echo_expected(false);
+        let buf = ingress.write_buf();
+        let data = b"+CFUN: 1\r\n\r\nOK\r\nAT\r\n\r\nCONNECT OK\r\n";
+        buf[..data.len()].copy_from_slice(data);
+        ingress.try_advance(data.len()).unwrap();
+
+        assert_eq!(Response::ok(b"+CFUN: 1"), *res_slot.try_get().unwrap());
+        assert!(res_slot.echo_expected());
+        assert_eq!(Urc::ConnectOk, sub.try_next_message_pure().unwrap());
+        assert_eq!(0, ingress.pos);
+    }
+
+    #[test]
+    fn urc_message_len() {
+        let res_slot = ResponseSlot::<100>::new();
+        let urc_channel = UrcChannel::<Urc, 10, 1>::new();
+        let mut buf = [0; 100];
+
+        let mut ingress: Ingress<_, Urc, 100, 10, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+        let mut sub = urc_channel.subscribe().unwrap();
+
+        let buf = ingress.write_buf();
+        let data = b"\r\nCONNECT OK\r\n\r\nCONNECT FAIL\r\n";
+        buf[..data.len()].copy_from_slice(data);
+        ingress.try_advance(data.len()).unwrap();
+
+        assert_eq!(2, sub.available());
+        assert_eq!(
+            Some(UrcMessage {
+                urc: Urc::ConnectOk,
+                len: 10
+            }),
+            sub.try_next_message_with_len()
+        );
+        assert_eq!(
+            Some(UrcMessage {
+                urc: Urc::ConnectFail,
+                len: 12
+            }),
+            sub.try_next_message_with_len()
+        );
+        assert_eq!(None, sub.try_next_message_with_len());
+    }
+
+    #[tokio::test]
+    async fn urc_subscription_stream() {
+        use futures::StreamExt;
+
+        let res_slot = ResponseSlot::<100>::new();
+        let urc_channel = UrcChannel::<Urc, 10, 1>::new();
+        let mut buf = [0; 100];
+
+        let mut ingress: Ingress<_, Urc, 100, 10, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+        let mut sub = urc_channel.subscribe().unwrap();
+        assert!(sub.is_empty());
+        assert_eq!(10, sub.capacity());
+
+        ingress
+            .write(b"\r\nCONNECT OK\r\n\r\nCONNECT FAIL\r\n")
+            .await;
+
+        assert_eq!(2, sub.len());
+        assert_eq!(8, sub.free_capacity());
+        assert_eq!(Some(Urc::ConnectOk), sub.next().await);
+        assert_eq!(Some(Urc::ConnectFail), sub.next().await);
+        assert!(sub.is_empty());
+    }
+
+    #[test]
+    fn process_calls_closure_for_each_frame() {
+        let res_slot = ResponseSlot::<100>::new();
+        let urc_channel = UrcChannel::<Urc, 10, 1>::new();
+        let mut buf = [0; 100];
+
+        let mut ingress: Ingress<_, Urc, 100, 10, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+        let data = b"\r\nCONNECT OK\r\n\r\nOK\r\n\r\nCONNECT FAIL\r\n\r\nCONN";
+        ingress.write_buf()[..data.len()].copy_from_slice(data);
+        ingress.pos = data.len();
+
+        let mut urcs = 0;
+        let mut responses = 0;
+        ingress.process(|result| match result {
+            DigestResult::Urc(_) => urcs += 1,
+            DigestResult::Response(Ok(_)) => responses += 1,
+            _ => {}
+        });
+
+        assert_eq!((urcs, responses), (2, 1));
+
+        // The incomplete frame is left in the buffer
+        assert_eq!(&ingress.buf[..ingress.pos], b"\r\nCONN");
+    }
+
+    #[test]
+    fn clear_urcs_keeps_pending_response() {
+        let res_slot = ResponseSlot::<100>::new();
+        let urc_channel = UrcChannel::<Urc, 10, 1>::new();
+        let mut buf = [0; 100];
+
+        let mut ingress: Ingress<_, Urc, 100, 10, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+        let mut sub = urc_channel.subscribe().unwrap();
+
+        let buf = ingress.write_buf();
+        let data = b"\r\nCONNECT OK\r\n\r\nCONNECT FAIL\r\n\r\nOK\r\n";
+        buf[..data.len()].copy_from_slice(data);
+        ingress.try_advance(data.len()).unwrap();
+
+        urc_channel.clear_urcs();
+
+        assert_eq!(None, sub.try_next_message_pure());
+        assert_eq!(10, urc_channel.free_capacity());
+
+        let response = res_slot.try_get().unwrap();
+        assert_eq!(Response::default(), *response);
+    }
+
+    #[test]
+    fn urc_overflow_policy() {
+        let data = b"\r\nCONNECT OK\r\n\r\nCONNECT FAIL\r\n\r\nCONNECT OK\r\n";
+
+        for (policy, expected) in [
+            (
+                UrcOverflowPolicy::KeepNewest,
+                [Urc::ConnectFail, Urc::ConnectOk],
+            ),
+            (
+                UrcOverflowPolicy::KeepOldest,
+                [Urc::ConnectOk, Urc::ConnectFail],
+            ),
+        ] {
+            let res_slot = ResponseSlot::<100>::new();
+            let urc_channel = UrcChannel::<Urc, 2, 1>::new_with_overflow_policy(policy);
+            let mut buf = [0; 100];
+
+            let mut ingress: Ingress<_, Urc, 100, 2, 1> =
+                Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+            let mut sub = urc_channel.subscribe().unwrap();
+
+            ingress.write_buf()[..data.len()].copy_from_slice(data);
+            ingress.try_advance(data.len()).unwrap();
+
+            assert_eq!(1, urc_channel.dropped_count());
+            assert_eq!(Some(expected[0].clone()), sub.try_next_message_pure());
+            assert_eq!(Some(expected[1].clone()), sub.try_next_message_pure());
+            assert_eq!(None, sub.try_next_message_pure());
+        }
+
+        // By default, nothing is dropped
+        let res_slot = ResponseSlot::<100>::new();
+        let urc_channel = UrcChannel::<Urc, 2, 1>::new();
+        let mut buf = [0; 100];
+
+        let mut ingress: Ingress<_, Urc, 100, 2, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+        let _sub = urc_channel.subscribe().unwrap();
+
+        ingress.write_buf()[..data.len()].copy_from_slice(data);
+        assert_eq!(Err(Error::UrcChannelFull), ingress.try_advance(data.len()));
+        assert_eq!(0, urc_channel.dropped_count());
+    }
+
+    #[test]
+    fn full_buffer_drops_oldest_bytes() {
+        let res_slot = ResponseSlot::<16>::new();
+        let urc_channel = UrcChannel::<Urc, 10, 1>::new();
+        let mut buf = [0; 16];
+
+        let mut ingress: Ingress<_, Urc, 16, 10, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+        // Data never forming a frame fills the buffer, and is dropped
+        let data = b"0123456789abcdef";
+        assert_eq!(Ok(data.len()), ingress.try_write(data));
+        assert_eq!(0, ingress.pos);
+
+        // Recovers on a subsequent valid frame
+        let data = b"0123456789abcdefgarbage\r\nOK\r\n";
+        assert_eq!(Ok(data.len()), ingress.try_write(data));
+
+        let response = res_slot.try_get().unwrap();
+        assert_eq!(Response::default(), *response);
+    }
+
+    #[test]
+    fn full_buffer_reports_dropped_bytes() {
+        static DROPPED: std::sync::Mutex<std::vec::Vec<u8>> =
+            std::sync::Mutex::new(std::vec::Vec::new());
+
+        let res_slot = ResponseSlot::<16>::new();
+        let urc_channel = UrcChannel::<Urc, 10, 1>::new();
+        let mut buf = [0; 16];
+
+        let digester = AtDigester::<Urc>::new().with_garbage_observer(|garbage| {
+            DROPPED.lock().unwrap().extend_from_slice(garbage);
+        });
+        let mut ingress: Ingress<_, Urc, 16, 10, 1> =
+            Ingress::new(digester, &mut buf, &res_slot, &urc_channel);
+
+        let data = b"0123456789abcdef";
+        assert_eq!(Ok(data.len()), ingress.try_write(data));
+        assert_eq!(0, ingress.pos);
+        assert_eq!(DROPPED.lock().unwrap().as_slice(), data);
+    }
+
+    #[test]
+    fn try_read_from_ingests_read_bytes() {
+        let res_slot = ResponseSlot::<100>::new();
+        let urc_channel = UrcChannel::<Urc, 10, 1>::new();
+        let mut buf = [0; 100];
+
+        let mut ingress: Ingress<_, Urc, 100, 10, 1> =
+            Ingress::new(AtDigester::<Urc>::new(), &mut buf, &res_slot, &urc_channel);
+
+        let mut sub = urc_channel.subscribe().unwrap();
+
+        struct Reader(&'static [u8]);
+        impl ErrorType for Reader {
+            type Error = embedded_io::ErrorKind;
+        }
+        impl embedded_io::Read for Reader {
+            fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                let len = buf.len().min(self.0.len());
+                buf[..len].copy_from_slice(&self.0[..len]);
+                self.0 = &self.0[len..];
+                Ok(len)
+            }
+        }
+
+        let mut r = Reader(b"\r\nCONNECT OK\r\n\r\nOK\r\n");
+        assert_eq!(Ok(20), ingress.try_read_from(&mut r));
+
+        assert_eq!(Some(Urc::ConnectOk), sub.try_next_message_pure());
+        assert_eq!(Response::default(), *res_slot.try_get().unwrap());
+        assert_eq!(Ok(0), ingress.try_read_from(&mut r));
+    }
+
     #[tokio::test]
     async fn read_from_can_recover_from_full_buffer() {
         let res_slot = ResponseSlot::<30>::new();
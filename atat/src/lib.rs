@@ -233,10 +233,13 @@ pub mod helpers;
 mod ingress;
 mod response;
 pub mod response_slot;
+#[cfg(feature = "derive")]
+mod scalar;
 mod traits;
 #[cfg(test)]
 mod tx_mock;
 pub mod urc_channel;
+mod wrapped;
 pub use nom;
 
 pub mod asynch;
@@ -260,13 +263,24 @@ pub use serde_at;
 pub use heapless;
 
 pub use config::Config;
-pub use digest::{AtDigester, AtDigester as DefaultDigester, DigestResult, Digester, Parser};
+pub use digest::{
+    parse_response, urc_len, AtDigester, AtDigester as DefaultDigester, DigestResult, Digester,
+    Parser,
+};
 pub use error::{CmeError, CmsError, ConnectionError, Error, InternalError};
 pub use ingress::{AtatIngress, Error as IngressError, Ingress};
 pub use response::Response;
 pub use response_slot::ResponseSlot;
+#[cfg(feature = "derive")]
+pub use scalar::ScalarResponse;
 pub use traits::{AtatCmd, AtatResp, AtatUrc};
-pub use urc_channel::{UrcChannel, UrcSubscription};
+pub use urc_channel::{UrcChannel, UrcMessage, UrcOverflowPolicy, UrcSubscription};
+pub use wrapped::Wrapped;
+
+/// Maximum number of bytes of the last sent command retained by the clients,
+/// see `Client::last_command`.
+#[cfg(feature = "last-command")]
+pub const LAST_COMMAND_LEN: usize = 64;
 
 #[cfg(test)]
 #[cfg(feature = "defmt")]
@@ -11,6 +11,7 @@ pub enum Response<const N: usize> {
     TimeoutError,
     InvalidResponseError,
     AbortedError,
+    RebootedError,
     ParseError,
     CapacityError,
     OtherError,
@@ -55,6 +56,7 @@ impl<'a, const N: usize> From<InternalError<'a>> for Response<N> {
             InternalError::Timeout => Response::TimeoutError,
             InternalError::InvalidResponse => Response::InvalidResponseError,
             InternalError::Aborted => Response::AbortedError,
+            InternalError::Rebooted => Response::RebootedError,
             InternalError::Parse => Response::ParseError,
             InternalError::Capacity => Response::CapacityError,
             InternalError::Error => Response::OtherError,
@@ -76,6 +78,7 @@ impl<'a, const N: usize> From<&'a Response<N>> for Result<&'a [u8], InternalErro
             Response::TimeoutError => Err(InternalError::Timeout),
             Response::InvalidResponseError => Err(InternalError::InvalidResponse),
             Response::AbortedError => Err(InternalError::Aborted),
+            Response::RebootedError => Err(InternalError::Rebooted),
             Response::ParseError => Err(InternalError::Parse),
             Response::CapacityError => Err(InternalError::Capacity),
             Response::OtherError => Err(InternalError::Error),
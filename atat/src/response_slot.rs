@@ -1,3 +1,4 @@
+use core::sync::atomic::{AtomicBool, Ordering};
 use embassy_sync::{
     blocking_mutex::raw::CriticalSectionRawMutex,
     mutex::{Mutex, MutexGuard},
@@ -10,6 +11,8 @@ use crate::{InternalError, Response};
 pub struct ResponseSlot<const N: usize>(
     Mutex<CriticalSectionRawMutex, Response<N>>,
     Signal<CriticalSectionRawMutex, ()>,
+    AtomicBool,
+    AtomicBool,
 );
 
 pub type ResponseSlotGuard<'a, const N: usize> =
@@ -26,7 +29,12 @@ impl<const N: usize> Default for ResponseSlot<N> {
 
 impl<const N: usize> ResponseSlot<N> {
     pub const fn new() -> Self {
-        Self(Mutex::new(Response::Ok(Vec::new())), Signal::new())
+        Self(
+            Mutex::new(Response::Ok(Vec::new())),
+            Signal::new(),
+            AtomicBool::new(true),
+            AtomicBool::new(true),
+        )
     }
 
     /// Reset the current response slot
@@ -52,6 +60,24 @@ impl<const N: usize> ResponseSlot<N> {
         }
     }
 
+    /// Set whether the modem echoes commands, see
+    /// [`Config::echo_enabled`](crate::Config::echo_enabled). Echo is expected
+    /// accordingly whenever no command is awaiting a response.
+    pub(crate) fn set_echo_enabled(&self, enabled: bool) {
+        self.3.store(enabled, Ordering::Relaxed);
+        self.2.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Set whether the command currently awaiting a response is echoed, until
+    /// its response is signaled
+    pub(crate) fn set_echo_expected(&self, expected: bool) {
+        self.2.store(expected, Ordering::Relaxed);
+    }
+
+    pub(crate) fn echo_expected(&self) -> bool {
+        self.2.load(Ordering::Relaxed)
+    }
+
     pub(crate) fn signal_prompt(&self, prompt: u8) -> Result<(), SlotInUseError> {
         if self.1.signaled() {
             return Err(SlotInUseError);
@@ -76,6 +102,10 @@ impl<const N: usize> ResponseSlot<N> {
         // Not currently signaled: We know that the client is not currently holding the response slot guard
         *self.0.try_lock().unwrap() = response.into();
 
+        // The command is complete, so restore the echo expected when idle
+        self.2
+            .store(self.3.load(Ordering::Relaxed), Ordering::Relaxed);
+
         // Mutex is unlocked before we signal
         self.1.signal(());
         Ok(())
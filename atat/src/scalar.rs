@@ -0,0 +1,67 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use serde_at::serde::{de, Deserialize, Deserializer};
+
+use crate::AtatResp;
+
+/// Response holding a single value, eg. `+CMD: 5`, for queries where
+/// declaring a dedicated response struct is overkill.
+///
+/// Example:
+/// ```
+/// use atat::atat_derive::AtatCmd;
+/// use atat::{AtatCmd, ScalarResponse};
+///
+/// #[derive(AtatCmd)]
+/// #[at_cmd("+CMD?", ScalarResponse<u8>)]
+/// struct GetValue;
+///
+/// assert_eq!(Ok(ScalarResponse(5)), GetValue.parse(Ok(b"+CMD: 5")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScalarResponse<T>(pub T);
+
+impl<T> Deref for ScalarResponse<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> AtatResp for ScalarResponse<T> {}
+
+impl<'de, T> Deserialize<'de> for ScalarResponse<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_newtype_struct("ScalarResponse", ScalarResponseVisitor::<T>(PhantomData))
+    }
+}
+
+struct ScalarResponseVisitor<T>(PhantomData<T>);
+
+impl<'de, T> de::Visitor<'de> for ScalarResponseVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = ScalarResponse<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a single value, e.g.: \"+CMD: 5\"")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(ScalarResponse)
+    }
+}
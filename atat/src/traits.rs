@@ -1,4 +1,4 @@
-use crate::error::{Error, InternalError};
+use crate::error::{CmeError, Error, InternalError};
 use heapless::{String, Vec};
 
 /// This trait needs to be implemented for every response type.
@@ -69,26 +69,107 @@ pub trait AtatCmd {
     /// using `send_retry`.
     const ATTEMPTS: u8 = 1;
 
+    /// Factor by which `send_retry` scales the timeout of each reattempt,
+    /// giving a slow modem more time to recover, eg. with a multiplier of 2
+    /// the third attempt waits for `4 * MAX_TIMEOUT_MS`.
+    const RETRY_TIMEOUT_MULTIPLIER: u32 = 1;
+
     /// Whether or not to reattempt a command on a parse error
     /// using `send_retry`.
     const REATTEMPT_ON_PARSE_ERR: bool = true;
 
+    /// `CmeError`s that should be considered transient, causing the command
+    /// to be reattempted using `send_retry`.
+    const RETRIABLE_CME_ERRORS: &'static [CmeError] = &[];
+
+    /// The termination written by [`AtatCmd::write`], when the command does
+    /// not specify one of its own. Clients replace it with
+    /// [`Config::default_termination`](crate::Config::default_termination).
+    ///
+    /// `None` if the termination is fixed by the command.
+    const DEFAULT_TERMINATION: Option<&'static str> = None;
+
+    /// Whether `send_retry` should reattempt the command after failing with
+    /// `e`.
+    ///
+    /// By default only [`Error::Timeout`] is considered retriable.
+    fn is_retriable_error(e: &Error) -> bool {
+        matches!(e, Error::Timeout)
+    }
+
     /// Force client to look for a response.
     /// Empty slice is then passed to parse by client.
     /// Implemented to enhance expandability of ATAT
     const EXPECTS_RESPONSE_CODE: bool = true;
 
+    /// Whether or not the modem echoes this command.
+    ///
+    /// When `false`, echo stripping in the digester is disabled while waiting
+    /// for the response to this command, so a response line that is not
+    /// preceded by `\r\n` is not discarded as echo.
+    const EXPECTS_ECHO: bool = true;
+
+    /// Whether or not to skip the cooldown following this command.
+    ///
+    /// When `true`, the next command is sent without waiting for the
+    /// configured [`Config::cmd_cooldown`](crate::Config::cmd_cooldown).
+    const NO_COOLDOWN: bool = false;
+
+    /// Hint of the expected maximum length of the response in bytes.
+    ///
+    /// Sending the command through a client whose ingress response buffer is
+    /// smaller fails to compile, as such a response would fail with a
+    /// capacity error. The simple clients, whose buffer is a runtime slice,
+    /// fail with [`Error::Capacity`] before sending the command instead.
+    const EXPECTED_RESPONSE_LEN: Option<usize> = None;
+
+    /// The prefix written ahead of the command, eg. `AT`.
+    ///
+    /// Derived implementations serialize the command with this prefix, which
+    /// can be picked per instance at runtime through `cmd_prefix = <function>`,
+    /// eg. to select between `AT+` and `AT#` variants of a command. Manual
+    /// implementations can override this, and should use it in
+    /// [`AtatCmd::write`].
+    fn cmd_prefix(&self) -> &str {
+        "AT"
+    }
+
     /// Write the command and return the number of written bytes.
     fn write(&self, buf: &mut [u8]) -> usize;
 
     /// Parse the response into a `Self::Response` or `Error` instance.
+    ///
+    /// A successful response holds the data received ahead of the final
+    /// result code, with surrounding whitespace trimmed. An `OK` without any
+    /// intermediate data, meaning the command was accepted with nothing to
+    /// report, is therefore passed as `Ok(&[])`, which implementations can
+    /// match on explicitly. Commands not expecting a response code (see
+    /// [`AtatCmd::EXPECTS_RESPONSE_CODE`]) are also parsed from `Ok(&[])`.
     fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, Error>;
+
+    /// Serialize the command into `buf`, and report the actual length of the
+    /// command.
+    ///
+    /// As commands do not declare a maximum length of their own, `buf` is
+    /// meant to be the buffer the command is written into, eg. the client
+    /// buffer, making it possible to spot buffers that are sized well beyond
+    /// the commands actually sent.
+    ///
+    /// Fails with [`Error::Overflow`] if the command does not fit in `buf`.
+    /// The default implementation defers to [`AtatCmd::write`], and thus
+    /// panics instead, so manual implementations should override it.
+    fn size_report(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        Ok(self.write(buf))
+    }
 }
 
 impl<T, const L: usize> AtatResp for Vec<T, L> where T: AtatResp {}
 
 impl<const L: usize> AtatResp for String<L> {}
 
+#[cfg(feature = "derive")]
+impl<T> AtatResp for serde_at::de::tracked::Tracked<T> where T: AtatResp {}
+
 impl<const L: usize> AtatCmd for String<L> {
     type Response = String<256>;
 
@@ -99,6 +180,15 @@ impl<const L: usize> AtatCmd for String<L> {
         len
     }
 
+    fn size_report(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let bytes = self.as_bytes();
+        let len = bytes.len();
+        buf.get_mut(..len)
+            .ok_or(Error::Overflow)?
+            .copy_from_slice(bytes);
+        Ok(len)
+    }
+
     fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, Error> {
         let utf8_string =
             core::str::from_utf8(resp.map_err(Error::from)?).map_err(|_| Error::Parse)?;
@@ -110,7 +200,7 @@ impl<const L: usize> AtatCmd for String<L> {
 mod test {
     use super::*;
     use crate as atat;
-    use atat_derive::{AtatEnum, AtatResp};
+    use atat_derive::{AtatCmd, AtatEnum, AtatResp};
     use heapless::String;
 
     #[derive(Debug, Clone, PartialEq, AtatEnum)]
@@ -158,6 +248,97 @@ mod test {
          * pub nslpi: Option<u8>, */
     }
 
+    #[derive(Debug, Clone, AtatResp, PartialEq)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    pub struct PDPContextDefinitionOptional {
+        #[at_arg(position = 0)]
+        pub cid: u8,
+        #[at_arg(position = 1)]
+        pub pdp_type: String<6>,
+        #[at_arg(position = 2)]
+        pub apn: String<99>,
+        #[at_arg(position = 3)]
+        pub pdp_addr: Option<String<39>>,
+        #[at_arg(position = 4)]
+        pub d_comp: Option<u8>,
+        #[at_arg(position = 5)]
+        pub h_comp: Option<u8>,
+        #[at_arg(position = 6)]
+        pub ipv4_addr_alloc: Option<u8>,
+    }
+
+    #[derive(Clone, AtatCmd)]
+    #[at_cmd("+CFUN", NoResponse)]
+    struct SetModuleFunctionality {
+        #[at_arg(position = 0)]
+        fun: u8,
+    }
+
+    #[derive(Debug, Clone, AtatResp, PartialEq)]
+    struct NoResponse;
+
+    #[test]
+    fn size_report() {
+        let cmd = String::<16>::try_from("AT+CSQ\r").unwrap();
+        assert_eq!(Ok(7), cmd.size_report(&mut [0; 64]));
+        assert_eq!(Err(Error::Overflow), cmd.size_report(&mut [0; 4]));
+    }
+
+    #[test]
+    fn size_report_derived() {
+        let cmd = SetModuleFunctionality { fun: 1 };
+        assert_eq!(Ok(10), cmd.size_report(&mut [0; 64]));
+        assert_eq!(Err(Error::Overflow), cmd.size_report(&mut [0; 4]));
+    }
+
+    #[test]
+    fn mixed_quoted_and_optional_fields() {
+        let resp: PDPContextDefinitionOptional =
+            serde_at::from_slice(b"+CGDCONT: 1,\"IP\",\"internet\",\"10.0.0.2\",0,0").unwrap();
+        assert_eq!(
+            resp,
+            PDPContextDefinitionOptional {
+                cid: 1,
+                pdp_type: String::try_from("IP").unwrap(),
+                apn: String::try_from("internet").unwrap(),
+                pdp_addr: Some(String::try_from("10.0.0.2").unwrap()),
+                d_comp: Some(0),
+                h_comp: Some(0),
+                ipv4_addr_alloc: None,
+            }
+        );
+
+        let resp: PDPContextDefinitionOptional =
+            serde_at::from_slice(b"+CGDCONT: 1,\"IP\",\"internet\",,0,1").unwrap();
+        assert_eq!(
+            resp,
+            PDPContextDefinitionOptional {
+                cid: 1,
+                pdp_type: String::try_from("IP").unwrap(),
+                apn: String::try_from("internet").unwrap(),
+                pdp_addr: None,
+                d_comp: Some(0),
+                h_comp: Some(1),
+                ipv4_addr_alloc: None,
+            }
+        );
+
+        let resp: PDPContextDefinitionOptional =
+            serde_at::from_slice(b"+CGDCONT: 1,\"IP\",\"internet\"").unwrap();
+        assert_eq!(
+            resp,
+            PDPContextDefinitionOptional {
+                cid: 1,
+                pdp_type: String::try_from("IP").unwrap(),
+                apn: String::try_from("internet").unwrap(),
+                pdp_addr: None,
+                d_comp: None,
+                h_comp: None,
+                ipv4_addr_alloc: None,
+            }
+        );
+    }
+
     #[test]
     fn single_multi_response() {
         let mut v = Vec::<_, 1>::from_slice(&[PDPContextState {
@@ -1,12 +1,123 @@
+use core::cell::Cell;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
-use embassy_sync::pubsub::{PubSubChannel, Publisher, Subscriber};
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::pubsub::{PubSubChannel, Publisher, Subscriber, WaitResult};
 
 use crate::AtatUrc;
 
 pub type UrcPublisher<'sub, Urc, const CAPACITY: usize, const SUBSCRIBERS: usize> =
-    Publisher<'sub, CriticalSectionRawMutex, Urc, CAPACITY, SUBSCRIBERS, 1>;
-pub type UrcSubscription<'sub, Urc, const CAPACITY: usize, const SUBSCRIBERS: usize> =
-    Subscriber<'sub, CriticalSectionRawMutex, Urc, CAPACITY, SUBSCRIBERS, 1>;
+    Publisher<'sub, CriticalSectionRawMutex, UrcMessage<Urc>, CAPACITY, SUBSCRIBERS, 1>;
+type UrcSubscriber<'sub, Urc, const CAPACITY: usize, const SUBSCRIBERS: usize> =
+    Subscriber<'sub, CriticalSectionRawMutex, UrcMessage<Urc>, CAPACITY, SUBSCRIBERS, 1>;
+
+/// A URC along with the length in bytes of the URC line it was parsed from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UrcMessage<Urc> {
+    pub urc: Urc,
+    /// Length of the URC line as digested, excluding the surrounding line
+    /// terminators
+    pub len: usize,
+}
+
+/// Subscription to the URCs published into a [`UrcChannel`].
+///
+/// Offers the same API as the underlying [`Subscriber`], including
+/// [`Stream`](futures::Stream), yielding URCs, with additional `_with_len`
+/// variants yielding the [`UrcMessage`] along with its length.
+pub struct UrcSubscription<'sub, Urc: AtatUrc, const CAPACITY: usize, const SUBSCRIBERS: usize> {
+    subscriber: UrcSubscriber<'sub, Urc, CAPACITY, SUBSCRIBERS>,
+}
+
+impl<Urc: AtatUrc, const CAPACITY: usize, const SUBSCRIBERS: usize>
+    UrcSubscription<'_, Urc, CAPACITY, SUBSCRIBERS>
+{
+    /// Wait for the next URC, or the number of URCs missed if the
+    /// subscription lagged behind.
+    pub async fn next_message(&mut self) -> WaitResult<Urc> {
+        match self.subscriber.next_message().await {
+            WaitResult::Lagged(n) => WaitResult::Lagged(n),
+            WaitResult::Message(message) => WaitResult::Message(message.urc),
+        }
+    }
+
+    /// Wait for the next URC, ignoring any lag.
+    pub async fn next_message_pure(&mut self) -> Urc {
+        self.subscriber.next_message_pure().await.urc
+    }
+
+    /// Wait for the next URC along with its length in bytes, ignoring any
+    /// lag.
+    pub async fn next_message_with_len(&mut self) -> UrcMessage<Urc> {
+        self.subscriber.next_message_pure().await
+    }
+
+    /// Get the next URC, or the number of URCs missed if the subscription
+    /// lagged behind, if any.
+    pub fn try_next_message(&mut self) -> Option<WaitResult<Urc>> {
+        self.subscriber
+            .try_next_message()
+            .map(|result| match result {
+                WaitResult::Lagged(n) => WaitResult::Lagged(n),
+                WaitResult::Message(message) => WaitResult::Message(message.urc),
+            })
+    }
+
+    /// Get the next URC, if any, ignoring any lag.
+    pub fn try_next_message_pure(&mut self) -> Option<Urc> {
+        self.try_next_message_with_len().map(|message| message.urc)
+    }
+
+    /// Get the next URC along with its length in bytes, if any, ignoring any
+    /// lag.
+    pub fn try_next_message_with_len(&mut self) -> Option<UrcMessage<Urc>> {
+        self.subscriber.try_next_message_pure()
+    }
+
+    /// The number of URCs pending for this subscription.
+    pub fn available(&self) -> u64 {
+        self.subscriber.available()
+    }
+
+    /// The maximum number of URCs the channel can hold.
+    pub fn capacity(&self) -> usize {
+        self.subscriber.capacity()
+    }
+
+    /// The free capacity of the channel.
+    pub fn free_capacity(&self) -> usize {
+        self.subscriber.free_capacity()
+    }
+
+    /// Drop all URCs currently queued in the channel.
+    pub fn clear(&self) {
+        self.subscriber.clear()
+    }
+
+    /// The number of URCs queued in the channel.
+    pub fn len(&self) -> usize {
+        self.subscriber.len()
+    }
+
+    /// Whether the channel is empty.
+    pub fn is_empty(&self) -> bool {
+        self.subscriber.is_empty()
+    }
+}
+
+impl<Urc: AtatUrc, const CAPACITY: usize, const SUBSCRIBERS: usize> futures::Stream
+    for UrcSubscription<'_, Urc, CAPACITY, SUBSCRIBERS>
+{
+    type Item = Urc;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        futures::Stream::poll_next(Pin::new(&mut self.subscriber), cx)
+            .map(|message| message.map(|message| message.urc))
+    }
+}
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -14,9 +125,27 @@ pub enum Error {
     MaximumSubscribersReached,
 }
 
-pub struct UrcChannel<Urc: AtatUrc, const CAPACITY: usize, const SUBSCRIBERS: usize>(
-    pub(crate) PubSubChannel<CriticalSectionRawMutex, Urc, CAPACITY, SUBSCRIBERS, 1>,
-);
+/// What to do with a URC received while the channel is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UrcOverflowPolicy {
+    /// Do not drop any URCs: `AtatIngress::try_advance` fails with
+    /// `Error::UrcChannelFull`, and `AtatIngress::advance` waits for the
+    /// subscribers to catch up.
+    #[default]
+    Backpressure,
+    /// Drop the oldest queued URC to make room for the new one.
+    KeepNewest,
+    /// Drop the new URC, keeping the queued ones.
+    KeepOldest,
+}
+
+pub struct UrcChannel<Urc: AtatUrc, const CAPACITY: usize, const SUBSCRIBERS: usize> {
+    pub(crate) channel:
+        PubSubChannel<CriticalSectionRawMutex, UrcMessage<Urc>, CAPACITY, SUBSCRIBERS, 1>,
+    overflow_policy: UrcOverflowPolicy,
+    dropped: Mutex<CriticalSectionRawMutex, Cell<usize>>,
+}
 
 impl<Urc: AtatUrc, const CAPACITY: usize, const SUBSCRIBERS: usize> Default
     for UrcChannel<Urc, CAPACITY, SUBSCRIBERS>
@@ -30,16 +159,76 @@ impl<Urc: AtatUrc, const CAPACITY: usize, const SUBSCRIBERS: usize>
     UrcChannel<Urc, CAPACITY, SUBSCRIBERS>
 {
     pub const fn new() -> Self {
-        Self(PubSubChannel::new())
+        Self::new_with_overflow_policy(UrcOverflowPolicy::Backpressure)
+    }
+
+    /// Create a channel dropping URCs according to `policy` when full,
+    /// rather than applying backpressure to the ingress.
+    pub const fn new_with_overflow_policy(policy: UrcOverflowPolicy) -> Self {
+        Self {
+            channel: PubSubChannel::new(),
+            overflow_policy: policy,
+            dropped: Mutex::new(Cell::new(0)),
+        }
     }
 
     pub fn subscribe(&self) -> Result<UrcSubscription<'_, Urc, CAPACITY, SUBSCRIBERS>, Error> {
-        self.0
+        self.channel
             .subscriber()
+            .map(|subscriber| UrcSubscription { subscriber })
             .map_err(|_| Error::MaximumSubscribersReached)
     }
 
     pub fn free_capacity(&self) -> usize {
-        self.0.free_capacity()
+        self.channel.free_capacity()
+    }
+
+    /// Number of URCs dropped so far because the channel was full, according
+    /// to the [`UrcOverflowPolicy`] of the channel.
+    ///
+    /// URCs discarded through [`UrcChannel::clear_urcs`] are not counted.
+    pub fn dropped_count(&self) -> usize {
+        self.dropped.lock(Cell::get)
+    }
+
+    /// Publish `urc`, parsed from a URC line of `len` bytes, through
+    /// `publisher`, applying the overflow policy if the channel is full. The
+    /// message is handed back if it should be published once there is room.
+    pub(crate) fn try_publish(
+        &self,
+        publisher: &UrcPublisher<'_, Urc, CAPACITY, SUBSCRIBERS>,
+        urc: Urc,
+        len: usize,
+    ) -> Result<(), UrcMessage<Urc>> {
+        let urc = UrcMessage { urc, len };
+        match self.overflow_policy {
+            UrcOverflowPolicy::Backpressure => publisher.try_publish(urc),
+            UrcOverflowPolicy::KeepNewest => {
+                if publisher.free_capacity() == 0 {
+                    self.count_dropped();
+                }
+                publisher.publish_immediate(urc);
+                Ok(())
+            }
+            UrcOverflowPolicy::KeepOldest => {
+                if publisher.try_publish(urc).is_err() {
+                    self.count_dropped();
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn count_dropped(&self) {
+        self.dropped.lock(|dropped| dropped.set(dropped.get() + 1));
+    }
+
+    /// Drop all URCs currently queued in the channel.
+    ///
+    /// Only the URC queue is affected; a response pending in the
+    /// [`ResponseSlot`](crate::ResponseSlot) is left untouched. Subscribers
+    /// that had not yet received the dropped URCs will observe them as lagged.
+    pub fn clear_urcs(&self) {
+        self.channel.clear();
     }
 }
@@ -0,0 +1,95 @@
+use crate::{AtatCmd, Error, InternalError};
+
+/// Command framing the serialized bytes of another command between a prefix
+/// and a suffix, eg. for protocols tunnelling a payload through a command
+/// such as `AT#SSEND=<id>`.
+///
+/// The response, timeouts, retry and echo behaviour are all those of the
+/// inner command.
+///
+/// The written length is the sum of the prefix, the bytes written by the
+/// inner command and the suffix, all of which must fit in the client buffer.
+///
+/// Example:
+/// ```
+/// use atat::{AtatCmd, Wrapped};
+/// use heapless::String;
+///
+/// let cmd = Wrapped {
+///     prefix: b"AT#SSEND=1,",
+///     inner: String::<16>::try_from("AT+CSQ").unwrap(),
+///     suffix: b"\r",
+/// };
+///
+/// let mut buf = [0; 32];
+/// let len = cmd.write(&mut buf);
+/// assert_eq!(&buf[..len], b"AT#SSEND=1,AT+CSQ\r");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wrapped<'a, C> {
+    /// Bytes written before the inner command
+    pub prefix: &'a [u8],
+    /// The wrapped command
+    pub inner: C,
+    /// Bytes written after the inner command
+    pub suffix: &'a [u8],
+}
+
+impl<C: AtatCmd> AtatCmd for Wrapped<'_, C> {
+    type Response = C::Response;
+
+    const CAN_ABORT: bool = C::CAN_ABORT;
+    const MAX_TIMEOUT_MS: u32 = C::MAX_TIMEOUT_MS;
+    const ATTEMPTS: u8 = C::ATTEMPTS;
+    const RETRY_TIMEOUT_MULTIPLIER: u32 = C::RETRY_TIMEOUT_MULTIPLIER;
+    const REATTEMPT_ON_PARSE_ERR: bool = C::REATTEMPT_ON_PARSE_ERR;
+    const RETRIABLE_CME_ERRORS: &'static [crate::CmeError] = C::RETRIABLE_CME_ERRORS;
+    const EXPECTS_RESPONSE_CODE: bool = C::EXPECTS_RESPONSE_CODE;
+    const EXPECTS_ECHO: bool = C::EXPECTS_ECHO;
+    const NO_COOLDOWN: bool = C::NO_COOLDOWN;
+    const EXPECTED_RESPONSE_LEN: Option<usize> = C::EXPECTED_RESPONSE_LEN;
+
+    fn is_retriable_error(e: &Error) -> bool {
+        C::is_retriable_error(e)
+    }
+
+    fn cmd_prefix(&self) -> &str {
+        self.inner.cmd_prefix()
+    }
+
+    fn write(&self, buf: &mut [u8]) -> usize {
+        let prefix_len = self.prefix.len();
+        buf[..prefix_len].copy_from_slice(self.prefix);
+        let len = prefix_len + self.inner.write(&mut buf[prefix_len..]);
+        buf[len..len + self.suffix.len()].copy_from_slice(self.suffix);
+        len + self.suffix.len()
+    }
+
+    fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, Error> {
+        self.inner.parse(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    #[test]
+    fn wraps_inner_command() {
+        let cmd = Wrapped {
+            prefix: b"AT#SSEND=1,",
+            inner: String::<16>::try_from("AT+CGMI").unwrap(),
+            suffix: b"\x1A",
+        };
+
+        let mut buf = [0; 32];
+        let len = cmd.write(&mut buf);
+        assert_eq!(b"AT#SSEND=1,AT+CGMI\x1A", &buf[..len]);
+
+        assert_eq!(
+            Ok(String::<256>::try_from("Quectel").unwrap()),
+            cmd.parse(Ok(b"Quectel"))
+        );
+    }
+}
@@ -3,7 +3,7 @@ use crate::proc_macro::TokenStream;
 use quote::quote;
 use syn::parse_macro_input;
 
-use crate::parse::{CmdAttributes, ParseInput};
+use crate::parse::{ArgAttributes, CmdAttributes, ParseInput};
 
 pub fn atat_cmd(input: TokenStream) -> TokenStream {
     let ParseInput {
@@ -19,14 +19,23 @@ pub fn atat_cmd(input: TokenStream) -> TokenStream {
         resp,
         parse,
         timeout_ms,
+        response_len,
         attempts,
+        retry_timeout_multiplier,
         reattempt_on_parse_err,
         abortable,
         response_code,
+        no_echo,
+        no_cooldown,
+        retriable_cme_errors,
+        retriable_error,
         value_sep,
         cmd_prefix,
+        cmd_prefix_fn,
         termination,
         escape_strings,
+        space_delimited_token,
+        expected_response_prefix,
     } = at_cmd.expect("missing #[at_cmd(...)] attribute");
 
     let ident_str = ident.to_string();
@@ -44,6 +53,15 @@ pub fn atat_cmd(input: TokenStream) -> TokenStream {
         None => quote! {},
     };
 
+    let response_len = match response_len {
+        Some(response_len) => {
+            quote! {
+                const EXPECTED_RESPONSE_LEN: Option<usize> = Some(#response_len);
+            }
+        }
+        None => quote! {},
+    };
+
     let abortable = match abortable {
         Some(abortable) => {
             quote! {
@@ -62,6 +80,15 @@ pub fn atat_cmd(input: TokenStream) -> TokenStream {
         None => quote! {},
     };
 
+    let retry_timeout_multiplier = match retry_timeout_multiplier {
+        Some(multiplier) => {
+            quote! {
+                const RETRY_TIMEOUT_MULTIPLIER: u32 = #multiplier;
+            }
+        }
+        None => quote! {},
+    };
+
     let response = match response_code {
         Some(is_resp) => {
             quote! {
@@ -71,6 +98,61 @@ pub fn atat_cmd(input: TokenStream) -> TokenStream {
         None => quote! {},
     };
 
+    let no_echo = match no_echo {
+        Some(no_echo) => {
+            quote! {
+                const EXPECTS_ECHO: bool = !#no_echo;
+            }
+        }
+        None => quote! {},
+    };
+
+    let no_cooldown = match no_cooldown {
+        Some(no_cooldown) => {
+            quote! {
+                const NO_COOLDOWN: bool = #no_cooldown;
+            }
+        }
+        None => quote! {},
+    };
+
+    let retriable_cme_errors = match retriable_cme_errors {
+        Some(retriable_cme_errors) => {
+            quote! {
+                const RETRIABLE_CME_ERRORS: &'static [atat::CmeError] =
+                    &[#(#retriable_cme_errors),*];
+            }
+        }
+        None => quote! {},
+    };
+
+    let retriable_error = match retriable_error {
+        Some(retriable_error) => {
+            quote! {
+                #[inline]
+                fn is_retriable_error(e: &atat::Error) -> bool {
+                    #retriable_error(e)
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    let cmd_prefix = match cmd_prefix_fn {
+        Some(cmd_prefix_fn) => quote! { #cmd_prefix_fn(self) },
+        None => quote! { #cmd_prefix },
+    };
+
+    let (termination, default_termination) = match termination {
+        Some(termination) => (termination, quote! {}),
+        None => (
+            String::from("\r"),
+            quote! {
+                const DEFAULT_TERMINATION: Option<&'static str> = Some("\r");
+            },
+        ),
+    };
+
     let reattempt_on_parse_err = match reattempt_on_parse_err {
         Some(reattempt_on_parse_err) => {
             quote! {
@@ -80,22 +162,52 @@ pub fn atat_cmd(input: TokenStream) -> TokenStream {
         None => quote! {},
     };
 
-    let (field_names, field_names_str): (Vec<_>, Vec<_>) = variants
+    let (field_values, field_names_str): (Vec<_>, Vec<_>) = variants
         .iter()
         .map(|f| {
             let ident = f.ident.clone().unwrap();
-            (ident.clone(), ident.to_string())
+            let value = match &f.attrs.at_arg {
+                Some(ArgAttributes {
+                    bool_as_onoff: true,
+                    ..
+                }) => quote! { &(if self.#ident { "ON" } else { "OFF" }) },
+                _ => quote! { &self.#ident },
+            };
+            // Fields left out are serialized as `None`, so that they are
+            // empty, or dropped altogether when trailing
+            let value = match &f.attrs.at_arg {
+                Some(ArgAttributes {
+                    depends_on: Some(depends_on),
+                    ..
+                }) => quote! { &(if #depends_on(self) { Some(#value) } else { None }) },
+                _ => value,
+            };
+            (value, ident.to_string())
         })
         .unzip();
 
+    let expected_response_prefix = match expected_response_prefix {
+        Some(prefix) => {
+            quote! {
+                if !resp.starts_with(#prefix.as_bytes()) {
+                    return Err(atat::Error::InvalidResponse);
+                }
+            }
+        }
+        None => quote! {},
+    };
+
     let parse = if let Some(parse) = parse {
         quote! {
             #[inline]
             fn parse(&self, res: Result<&[u8], atat::InternalError>) -> core::result::Result<Self::Response, atat::Error> {
                 match res {
-                    Ok(resp) => #parse(resp).map_err(|e| {
-                        atat::Error::Parse
-                    }),
+                    Ok(resp) => {
+                        #expected_response_prefix
+                        #parse(resp).map_err(|e| {
+                            atat::Error::Parse
+                        })
+                    }
                     Err(e) => Err(e.into())
                 }
             }
@@ -105,9 +217,16 @@ pub fn atat_cmd(input: TokenStream) -> TokenStream {
             #[inline]
            fn parse(&self, res: Result<&[u8], atat::InternalError>) -> core::result::Result<Self::Response, atat::Error> {
                match res {
-                   Ok(resp) => atat::serde_at::from_slice::<#resp>(resp).map_err(|e| {
-                       atat::Error::Parse
-                   }),
+                   Ok(resp) => {
+                       #expected_response_prefix
+                       let options = atat::serde_at::DeserializeOptions {
+                           space_delimited_token: #space_delimited_token,
+                           ..Default::default()
+                       };
+                       atat::serde_at::from_slice_with_options::<#resp>(resp, options).map_err(|e| {
+                           atat::Error::Parse
+                       })
+                   }
                    Err(e) => Err(e.into())
                }
            }
@@ -121,27 +240,52 @@ pub fn atat_cmd(input: TokenStream) -> TokenStream {
 
             #timeout
 
+            #response_len
+
             #abortable
 
             #attempts
 
+            #retry_timeout_multiplier
+
             #response
 
             #reattempt_on_parse_err
 
+            #no_echo
+
+            #no_cooldown
+
+            #retriable_cme_errors
+
+            #retriable_error
+
+            #default_termination
+
+            #[inline]
+            fn cmd_prefix(&self) -> &str {
+                #cmd_prefix
+            }
+
             #[inline]
             fn write(&self, buf: &mut [u8]) -> usize {
-                match atat::serde_at::to_slice(self, #cmd, buf, atat::serde_at::SerializeOptions {
-                    value_sep: #value_sep,
-                    cmd_prefix: #cmd_prefix,
-                    termination: #termination,
-                    escape_strings: #escape_strings
-                }) {
+                match atat::AtatCmd::size_report(self, buf) {
                     Ok(s) => s,
                     Err(_) => panic!("Failed to serialize command")
                 }
             }
 
+            #[inline]
+            fn size_report(&self, buf: &mut [u8]) -> core::result::Result<usize, atat::Error> {
+                let options = atat::serde_at::SerializeOptions::default()
+                    .value_sep(#value_sep)
+                    .cmd_prefix(atat::AtatCmd::cmd_prefix(self))
+                    .termination(#termination)
+                    .escape_strings(#escape_strings);
+                atat::serde_at::to_slice(self, #cmd, buf, options)
+                    .map_err(|_| atat::Error::Overflow)
+            }
+
             #parse
         }
 
@@ -165,7 +309,7 @@ pub fn atat_cmd(input: TokenStream) -> TokenStream {
                     atat::serde_at::serde::ser::SerializeStruct::serialize_field(
                         &mut serde_state,
                         #field_names_str,
-                        &self.#field_names,
+                        #field_values,
                     )?;
                 )*
 
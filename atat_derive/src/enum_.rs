@@ -29,11 +29,13 @@ pub fn atat_enum(input: TokenStream) -> TokenStream {
         ..
     } = parse_macro_input!(input as ParseInput);
 
-    let repr = at_enum
-        .unwrap_or_else(|| EnumAttributes {
-            repr: format_ident!("u8"),
-        })
-        .repr;
+    let EnumAttributes {
+        repr,
+        no_discriminant,
+    } = at_enum.unwrap_or_else(|| EnumAttributes {
+        repr: format_ident!("u8"),
+        no_discriminant: false,
+    });
     let ident_str = ident.to_string();
 
     let variant_names_str: Vec<_> = variants
@@ -122,17 +124,33 @@ pub fn atat_enum(input: TokenStream) -> TokenStream {
                 // info.deserialize_match_arms.push(quote! {(#anon_enum::anon_ident, __variant) => Ok(#ident::#variant_ident)});
                 // helpers::deserialize_struct(ident.clone(), Vec::new(), &generics);
 
-                info.serialize_match_arms.push(quote! {
-                    #ident::#variant_ident { #(ref #field_ident),* } => {
-                        let mut serde_state = atat::serde_at::serde::ser::Serializer::serialize_struct_variant(serializer, #ident_str, #val as u32, #variant_ident_str, 0)?;
-                        #(
-                            atat::serde_at::serde::ser::SerializeStructVariant::serialize_field(
-                                &mut serde_state,
-                                #field_str,
-                                #field_ident,
-                            )?;
-                        )*
-                        atat::serde_at::serde::ser::SerializeStructVariant::end(serde_state)
+                info.serialize_match_arms.push(if no_discriminant {
+                    let len = field_ident.len();
+                    quote! {
+                        #ident::#variant_ident { #(ref #field_ident),* } => {
+                            let mut serde_state = atat::serde_at::serde::ser::Serializer::serialize_tuple(serializer, #len)?;
+                            #(
+                                atat::serde_at::serde::ser::SerializeTuple::serialize_element(
+                                    &mut serde_state,
+                                    #field_ident,
+                                )?;
+                            )*
+                            atat::serde_at::serde::ser::SerializeTuple::end(serde_state)
+                        }
+                    }
+                } else {
+                    quote! {
+                        #ident::#variant_ident { #(ref #field_ident),* } => {
+                            let mut serde_state = atat::serde_at::serde::ser::Serializer::serialize_struct_variant(serializer, #ident_str, #val as u32, #variant_ident_str, 0)?;
+                            #(
+                                atat::serde_at::serde::ser::SerializeStructVariant::serialize_field(
+                                    &mut serde_state,
+                                    #field_str,
+                                    #field_ident,
+                                )?;
+                            )*
+                            atat::serde_at::serde::ser::SerializeStructVariant::end(serde_state)
+                        }
                     }
                 });
             }
@@ -189,16 +207,31 @@ pub fn atat_enum(input: TokenStream) -> TokenStream {
                     }
                 });
 
-                info.serialize_match_arms.push(quote! {
-                    #ident::#variant_ident ( #(ref #anon_fields),* ) => {
-                        let mut serde_state = atat::serde_at::serde::ser::Serializer::serialize_tuple_variant(serializer, #ident_str, #val as u32, #variant_ident_str, 0)?;
-                        #(
-                            atat::serde_at::serde::ser::SerializeTupleVariant::serialize_field(
-                                &mut serde_state,
-                                #anon_fields,
-                            )?;
-                        )*
-                        atat::serde_at::serde::ser::SerializeTupleVariant::end(serde_state)
+                info.serialize_match_arms.push(if no_discriminant {
+                    quote! {
+                        #ident::#variant_ident ( #(ref #anon_fields),* ) => {
+                            let mut serde_state = atat::serde_at::serde::ser::Serializer::serialize_tuple(serializer, #variant_fields_len)?;
+                            #(
+                                atat::serde_at::serde::ser::SerializeTuple::serialize_element(
+                                    &mut serde_state,
+                                    #anon_fields,
+                                )?;
+                            )*
+                            atat::serde_at::serde::ser::SerializeTuple::end(serde_state)
+                        }
+                    }
+                } else {
+                    quote! {
+                        #ident::#variant_ident ( #(ref #anon_fields),* ) => {
+                            let mut serde_state = atat::serde_at::serde::ser::Serializer::serialize_tuple_variant(serializer, #ident_str, #val as u32, #variant_ident_str, 0)?;
+                            #(
+                                atat::serde_at::serde::ser::SerializeTupleVariant::serialize_field(
+                                    &mut serde_state,
+                                    #anon_fields,
+                                )?;
+                            )*
+                            atat::serde_at::serde::ser::SerializeTupleVariant::end(serde_state)
+                        }
                     }
                 });
             }
@@ -65,11 +65,15 @@ pub fn derive_atat_resp(input: TokenStream) -> TokenStream {
 /// `#[at_urc(..)]`, that is used to specify the URC token to match for.
 ///
 /// The first argument is required, and must be either a string or a byte
-/// literal, specifying the URC token to match for.
+/// literal, specifying the URC token to match for. The token includes its
+/// prefix, which is not limited to `+`, eg. `#[at_urc("#SGACT")]` for Telit
+/// modems.
 ///
 /// Allowed optionals for `at_urc` are:
 /// - `parse`: **function** Function that should be used to parse for the URC
-///   instead of using default `atat::digest::parser::urc_helper` function. The
+///   instead of using default `atat::digest::parser::urc_helper` function.
+///   Unlike the default, it is not passed the line terminator of the
+///   digester, so it only matches `\r\n` terminated lines. The
 ///   passed functions need to have a valid `nom` signature `(arguments) -> impl Fn(Input) -> IResult<Input, Output, Error>`.
 #[proc_macro_derive(AtatUrc, attributes(at_urc))]
 pub fn derive_atat_urc(input: TokenStream) -> TokenStream {
@@ -126,6 +130,12 @@ pub fn derive_atat_urc(input: TokenStream) -> TokenStream {
 ///
 /// **Note**: `at_enum` defaults to `u8`
 ///
+/// Additionally the flag `no_discriminant` can be given, eg.
+/// `#[at_enum(u8, no_discriminant)]`, in which case tuple and struct variants
+/// are serialized as their fields only, without the leading discriminant
+/// value. This only affects serialization; deserialization still expects the
+/// discriminant.
+///
 /// ### Field attribute (`#[at_arg(..)]`)
 /// The `AtatEnum` derive macro comes with an optional field attribute
 /// `#[at_arg(..)]`, that can be specified for some or all of the fields.
@@ -148,7 +158,13 @@ pub fn derive_atat_enum(input: TokenStream) -> TokenStream {
 ///
 /// This container attribute requires specifying at least a command and an
 /// expected response struct as: `#[at_cmd("+USORD", SocketData)]` where
-/// `SocketData` is any type implementing `AtatResp`.
+/// `SocketData` is any type implementing `AtatResp`. The command includes
+/// its prefix, which is not limited to `+`, eg. `#[at_cmd("#SGACT?", ..)]`.
+///
+/// The command may be empty, eg. `#[at_cmd("", NoResponse)]`, for the bare
+/// `AT` keepalive or for commands where the parameters follow the prefix
+/// directly (`AT<params>`). An empty command is never followed by the value
+/// separator, regardless of `value_sep`.
 ///
 /// Furthermore the container attribute allows specifying some additional
 /// options to tweak the command. All optional attributes takes the form `<key>
@@ -157,16 +173,44 @@ pub fn derive_atat_enum(input: TokenStream) -> TokenStream {
 /// Allowed options are:
 /// - `timeout_ms`: **integer** The maximum timeout in milliseconds of the
 ///   command
+/// - `retry_timeout_multiplier`: **integer** Factor by which `send_retry`
+///   scales the timeout of each reattempt (default 1)
+/// - `response_len`: **integer** Hint of the expected maximum length of the
+///   response in bytes
 /// - `abortable`: **bool** Whether or not the command can be aborted
+/// - `retriable_cme_errors`: **array** `CmeError`s on which `send_retry`
+///   should reattempt the command, eg.
+///   `retriable_cme_errors = [atat::CmeError::SimBusy]`
+/// - `retriable_error`: **function** Predicate `fn(&atat::Error) -> bool`
+///   deciding which errors `send_retry` should reattempt the command on
+///   (default: only `Error::Timeout`), eg.
+///   `retriable_error = my_crate::is_transient`
+/// - `no_echo`: **bool** Whether the modem never echoes this command (default
+///   false). When set, the digester does not strip a leading line as echo
+///   while waiting for the response to this command, even if echo is
+///   otherwise enabled on the modem.
+/// - `no_cooldown`: **bool** Skip the inter-command cooldown following this
+///   command (default false). Useful to speed up back-to-back commands that
+///   the modem handles fine, eg. during initialization.
 /// - `value_sep`: **bool** Disable the seperator between the command and any
 ///   parameters (default true). Useful to create "fixed" commands, eg.
 ///   `#[at_cmd("+UDCONF=1", NoResponse, value_sep = false)]`.
 /// - `cmd_prefix`: **string** Overwrite the prefix of the command (default
-///   'AT'). Can also be set to '' (empty).
+///   'AT'). Can also be set to '' (empty), or to a function
+///   `fn(&Self) -> &str` picking the prefix of each instance at runtime, eg.
+///   `cmd_prefix = Self::prefix`.
 /// - `termination`: **string** Overwrite the line termination of the command
-///   (default '\r'). Can also be set to '' (empty).
+///   (default '\r', replaced by the clients with `Config::default_termination`,
+///   '\r\n' unless configured, when not set). Can also be set to '' (empty).
+/// - `expected_response_prefix`: **string** Prefix the response data must
+///   start with, eg. `"+CSQ:"`. Responses not starting with it are reported as
+///   `Error::InvalidResponse` rather than parsed, guarding against a late
+///   response to a previous command being mistaken for this one.
 /// - `escape_strings`: **bool** Whether to escape and quote strings in commands
 ///   (default true).
+/// - `space_delimited_token`: **bool** Whether to accept a response identifier
+///   delimited by a space instead of a colon, eg. `+CMD 1,2` (default false).
+///   See `atat::serde_at::DeserializeOptions`.
 /// - `parse`: **function** Function that should be used to parse the response
 ///   instead of using default `atat::serde_at::from_slice` function. The
 ///   passed functions needs to have a signature `Result<Response, E>` where
@@ -181,6 +225,13 @@ pub fn derive_atat_enum(input: TokenStream) -> TokenStream {
 ///   string. (eg. for command `AT+CMD=a,b`, field `a` would have `position = 1`
 ///   and field `b` would have `position = 2`) (defaults to order of the fields
 ///   in the struct)
+/// - bool_as: **string** Alternative representation of a `bool` field. Only
+///   `"onoff"` is supported, serializing the field as the quoted `"ON"` or
+///   `"OFF"`, eg. `#[at_arg(bool_as = "onoff")]`
+/// - depends_on: **function** Predicate `fn(&Self) -> bool` deciding whether
+///   the field is sent, for parameters only valid depending on the value of
+///   other parameters, eg. `#[at_arg(depends_on = Self::is_manual)]`. A field
+///   left out is sent as empty, or dropped when trailing, like `None`.
 #[proc_macro_derive(AtatCmd, attributes(at_cmd, at_arg))]
 pub fn derive_atat_cmd(input: TokenStream) -> TokenStream {
     cmd::atat_cmd(input)
@@ -1,8 +1,10 @@
 use proc_macro2::Span;
+use quote::format_ident;
 use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
 use syn::{
-    Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, ExprLit, ExprPath, Fields, Generics,
-    Ident, Lit, LitByteStr, Path, Type,
+    bracketed, Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, ExprLit, ExprPath, Fields,
+    Generics, Ident, Lit, LitByteStr, Path, Token, Type,
 };
 
 #[derive(Clone)]
@@ -21,14 +23,23 @@ pub struct CmdAttributes {
     pub resp: Path,
     pub parse: Option<Path>,
     pub timeout_ms: Option<u32>,
+    pub response_len: Option<usize>,
     pub attempts: Option<u8>,
+    pub retry_timeout_multiplier: Option<u32>,
     pub abortable: Option<bool>,
     pub reattempt_on_parse_err: Option<bool>,
     pub response_code: Option<bool>,
+    pub no_echo: Option<bool>,
+    pub no_cooldown: Option<bool>,
+    pub retriable_cme_errors: Option<Vec<Path>>,
+    pub retriable_error: Option<Path>,
     pub value_sep: bool,
     pub cmd_prefix: String,
-    pub termination: String,
+    pub cmd_prefix_fn: Option<Path>,
+    pub termination: Option<String>,
     pub escape_strings: bool,
+    pub space_delimited_token: bool,
+    pub expected_response_prefix: Option<String>,
 }
 /// Parsed attributes of `#[at_arg(..)]`
 #[derive(Clone)]
@@ -36,6 +47,12 @@ pub struct ArgAttributes {
     pub value: Option<i64>,
     pub position: Option<usize>,
     pub default: bool,
+    /// Serialize a `bool` as the quoted `"ON"`/`"OFF"`, set by
+    /// `bool_as = "onoff"`
+    pub bool_as_onoff: bool,
+    /// Predicate `fn(&Self) -> bool` deciding whether the field is sent, set
+    /// by `depends_on = path`
+    pub depends_on: Option<Path>,
 }
 
 /// Parsed attributes of `#[at_urc(..)]`
@@ -49,6 +66,7 @@ pub struct UrcAttributes {
 #[derive(Clone)]
 pub struct EnumAttributes {
     pub repr: Ident,
+    pub no_discriminant: bool,
 }
 
 /// Parsed field level attributes
@@ -162,6 +180,8 @@ impl Parse for ArgAttributes {
             value: None,
             position: None,
             default: false,
+            bool_as_onoff: false,
+            depends_on: None,
         };
 
         while {
@@ -201,6 +221,30 @@ impl Parse for ArgAttributes {
                 syn::Meta::Path(path) if path.is_ident("default") => {
                     attrs.default = true;
                 }
+                syn::Meta::NameValue(name_value) if name_value.path.is_ident("bool_as") => {
+                    match name_value.value.clone() {
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(v), ..
+                        }) if v.value() == "onoff" => attrs.bool_as_onoff = true,
+                        _ => {
+                            return Err(Error::new(
+                                Span::call_site(),
+                                "bool_as argument must be \"onoff\"",
+                            ))
+                        }
+                    }
+                }
+                syn::Meta::NameValue(name_value) if name_value.path.is_ident("depends_on") => {
+                    match name_value.value.clone() {
+                        Expr::Path(ExprPath { path, .. }) => attrs.depends_on = Some(path),
+                        _ => {
+                            return Err(Error::new(
+                                Span::call_site(),
+                                "depends_on argument must be a function",
+                            ))
+                        }
+                    }
+                }
                 _ => return Err(Error::new(Span::call_site(), "unknown argument!")),
             }
 
@@ -211,6 +255,28 @@ impl Parse for ArgAttributes {
     }
 }
 
+impl Parse for EnumAttributes {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attrs = Self {
+            repr: format_ident!("u8"),
+            no_discriminant: false,
+        };
+
+        while {
+            let ident = input.parse::<Ident>()?;
+            if ident == "no_discriminant" {
+                attrs.no_discriminant = true;
+            } else {
+                attrs.repr = ident;
+            }
+
+            input.parse::<syn::token::Comma>().is_ok()
+        } {}
+
+        Ok(attrs)
+    }
+}
+
 impl Parse for UrcAttributes {
     fn parse(input: ParseStream) -> Result<Self> {
         let code = match input.parse::<syn::Lit>() {
@@ -253,17 +319,41 @@ impl Parse for CmdAttributes {
             resp: response_ident,
             parse: None,
             timeout_ms: None,
+            response_len: None,
             attempts: None,
+            retry_timeout_multiplier: None,
             abortable: None,
             reattempt_on_parse_err: None,
             response_code: None,
+            no_echo: None,
+            no_cooldown: None,
+            retriable_cme_errors: None,
+            retriable_error: None,
             value_sep: true,
             cmd_prefix: String::from("AT"),
-            termination: String::from("\r"),
+            cmd_prefix_fn: None,
+            termination: None,
             escape_strings: true,
+            space_delimited_token: false,
+            expected_response_prefix: None,
         };
 
         while input.parse::<syn::token::Comma>().is_ok() {
+            // Parsed by hand, as array expressions require syn's "full" feature
+            if input
+                .fork()
+                .parse::<Ident>()
+                .is_ok_and(|i| i == "retriable_cme_errors")
+            {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                let content;
+                bracketed!(content in input);
+                let errors = Punctuated::<Path, Token![,]>::parse_terminated(&content)?;
+                at_cmd.retriable_cme_errors = Some(errors.into_iter().collect());
+                continue;
+            }
+
             let optional = input.parse::<syn::MetaNameValue>()?;
             if optional.path.is_ident("timeout_ms") {
                 match optional.value {
@@ -279,6 +369,20 @@ impl Parse for CmdAttributes {
                         ))
                     }
                 }
+            } else if optional.path.is_ident("response_len") {
+                match optional.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(v), ..
+                    }) => {
+                        at_cmd.response_len = Some(v.base10_parse().unwrap());
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            Span::call_site(),
+                            "expected integer value for 'response_len'",
+                        ))
+                    }
+                }
             } else if optional.path.is_ident("attempts") {
                 match optional.value {
                     Expr::Lit(ExprLit {
@@ -293,6 +397,20 @@ impl Parse for CmdAttributes {
                         ))
                     }
                 }
+            } else if optional.path.is_ident("retry_timeout_multiplier") {
+                match optional.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(v), ..
+                    }) => {
+                        at_cmd.retry_timeout_multiplier = Some(v.base10_parse().unwrap());
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            Span::call_site(),
+                            "expected integer value for 'retry_timeout_multiplier'",
+                        ))
+                    }
+                }
             } else if optional.path.is_ident("parse") {
                 match optional.value {
                     Expr::Path(ExprPath { path, .. }) => {
@@ -354,10 +472,27 @@ impl Parse for CmdAttributes {
                     }) => {
                         at_cmd.cmd_prefix = v.value();
                     }
+                    Expr::Path(ExprPath { path, .. }) => {
+                        at_cmd.cmd_prefix_fn = Some(path);
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            Span::call_site(),
+                            "expected string value or function for 'cmd_prefix'",
+                        ))
+                    }
+                }
+            } else if optional.path.is_ident("expected_response_prefix") {
+                match optional.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(v), ..
+                    }) => {
+                        at_cmd.expected_response_prefix = Some(v.value());
+                    }
                     _ => {
                         return Err(Error::new(
                             Span::call_site(),
-                            "expected string value for 'cmd_prefix'",
+                            "expected string value for 'expected_response_prefix'",
                         ))
                     }
                 }
@@ -366,7 +501,7 @@ impl Parse for CmdAttributes {
                     Expr::Lit(ExprLit {
                         lit: Lit::Str(v), ..
                     }) => {
-                        at_cmd.termination = v.value();
+                        at_cmd.termination = Some(v.value());
                     }
                     _ => {
                         return Err(Error::new(
@@ -389,6 +524,20 @@ impl Parse for CmdAttributes {
                         ))
                     }
                 }
+            } else if optional.path.is_ident("space_delimited_token") {
+                match optional.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Bool(v), ..
+                    }) => {
+                        at_cmd.space_delimited_token = v.value;
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            Span::call_site(),
+                            "expected bool value for 'space_delimited_token'",
+                        ))
+                    }
+                }
             } else if optional.path.is_ident("response_code") {
                 match optional.value {
                     Expr::Lit(ExprLit {
@@ -403,6 +552,46 @@ impl Parse for CmdAttributes {
                         ))
                     }
                 }
+            } else if optional.path.is_ident("no_echo") {
+                match optional.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Bool(v), ..
+                    }) => {
+                        at_cmd.no_echo = Some(v.value);
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            Span::call_site(),
+                            "expected bool value for 'no_echo'",
+                        ))
+                    }
+                }
+            } else if optional.path.is_ident("no_cooldown") {
+                match optional.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Bool(v), ..
+                    }) => {
+                        at_cmd.no_cooldown = Some(v.value);
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            Span::call_site(),
+                            "expected bool value for 'no_cooldown'",
+                        ))
+                    }
+                }
+            } else if optional.path.is_ident("retriable_error") {
+                match optional.value {
+                    Expr::Path(ExprPath { path, .. }) => {
+                        at_cmd.retriable_error = Some(path);
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            Span::call_site(),
+                            "expected function for 'retriable_error'",
+                        ))
+                    }
+                }
             }
         }
 
@@ -422,9 +611,7 @@ impl Parse for ParseInput {
             if attr.path().is_ident("at_cmd") {
                 at_cmd = Some(attr.parse_args()?);
             } else if attr.path().is_ident("at_enum") {
-                at_enum = Some(EnumAttributes {
-                    repr: attr.parse_args()?,
-                });
+                at_enum = Some(attr.parse_args()?);
             }
         }
 
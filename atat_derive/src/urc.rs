@@ -56,7 +56,7 @@ pub fn atat_urc(input: TokenStream) -> TokenStream {
             }
         } else {
             quote! {
-                atat::digest::parser::urc_helper(&#code[..]),
+                atat::digest::parser::urc_helper_with(&#code[..], le),
             }
         };
 
@@ -83,6 +83,14 @@ pub fn atat_urc(input: TokenStream) -> TokenStream {
         impl #impl_generics atat::Parser for #ident #ty_generics #where_clause {
             fn parse<'a>(
                 buf: &'a [u8],
+            ) -> Result<(&'a [u8], usize), atat::digest::ParseError> {
+                <Self as atat::Parser>::parse_with_terminator(buf, b"\r\n")
+            }
+
+            #[allow(unused_variables)]
+            fn parse_with_terminator<'a>(
+                buf: &'a [u8],
+                le: &'static [u8],
             ) -> Result<(&'a [u8], usize), atat::digest::ParseError> {
                 let (_, r) = atat::nom::branch::alt((
                     #(
@@ -32,3 +32,11 @@ pub struct GetSoftwareVersion;
 #[derive(Clone, AtatCmd)]
 #[at_cmd("+UWAPMACADDR", WifiMac)]
 pub struct GetWifiMac;
+
+/// List current calls +CLCC
+///
+/// Returns an entry for each of the current calls, each on a separate
+/// `+CLCC:` line.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CLCC", atat::heapless::Vec<CallEntry, 4>)]
+pub struct ListCurrentCalls;
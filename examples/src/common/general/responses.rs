@@ -28,3 +28,22 @@ pub struct SoftwareVersion {
 pub struct WifiMac {
     pub mac_addr: atat::heapless_bytes::Bytes<12>,
 }
+
+/// Entry of the list of current calls +CLCC
+#[derive(Clone, Debug, AtatResp)]
+pub struct CallEntry {
+    /// Call identification number
+    pub id: u8,
+    /// 0: mobile originated call, 1: mobile terminated call
+    pub dir: u8,
+    /// State of the call, eg. 0: active, 4: incoming
+    pub stat: u8,
+    /// Bearer/teleservice, eg. 0: voice
+    pub mode: u8,
+    /// Whether the call is part of a multiparty (conference) call
+    pub mpty: u8,
+    /// Phone number
+    pub number: Option<String<32>>,
+    /// Type of address of the phone number, eg. 145 for international numbers
+    pub number_type: Option<u8>,
+}
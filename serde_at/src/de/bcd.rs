@@ -0,0 +1,142 @@
+use core::fmt;
+use core::ops::Deref;
+use serde::de::Visitor;
+use serde::{de, Deserialize};
+
+/// Number of bytes needed to hold any `u64` as packed BCD
+pub const BCD_MAX_BYTES: usize = 10;
+
+/// `Bcd`
+/// An unsigned integer encoded as packed binary-coded decimal, with one decimal
+/// digit per nibble, most significant digit first. Eg. `1234` is encoded as the
+/// bytes `[0x12, 0x34]`, and written in AT commands as the nibbles `1234`.
+/// Can be dereferenced to its value.
+///
+/// Trailing `F` filler nibbles, as used to pad SIM fields, are ignored when
+/// decoding, eg. `123F` is `123`. Empty values are rejected.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Bcd(pub u64);
+
+impl Bcd {
+    /// Encode the value as packed BCD bytes into `buf`, returning the used
+    /// part of it. Odd digit counts are padded with a leading zero nibble.
+    pub fn to_bytes(self, buf: &mut [u8; BCD_MAX_BYTES]) -> &[u8] {
+        let mut val = self.0;
+        let mut index = buf.len();
+        loop {
+            index -= 1;
+            buf[index] = (val % 10) as u8;
+            val /= 10;
+            buf[index] |= ((val % 10) as u8) << 4;
+            val /= 10;
+            if val == 0 {
+                break;
+            }
+        }
+        &buf[index..]
+    }
+
+    /// Decode packed BCD bytes. Returns `None` if there are no digits, if any
+    /// nibble is neither a decimal digit nor trailing `F` filler, or if the
+    /// value overflows.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_nibbles(bytes.iter().flat_map(|b| [b >> 4, b & 0x0F]))
+    }
+
+    fn from_nibbles(nibbles: impl Iterator<Item = u8>) -> Option<Self> {
+        let mut val: u64 = 0;
+        let mut digits = 0;
+        let mut filler = false;
+        for nibble in nibbles {
+            match nibble {
+                0xF => filler = true,
+                0..=9 if !filler => {
+                    val = val.checked_mul(10)?.checked_add(u64::from(nibble))?;
+                    digits += 1;
+                }
+                _ => return None,
+            }
+        }
+        (digits > 0).then_some(Self(val))
+    }
+}
+
+impl Deref for Bcd {
+    type Target = u64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+struct BcdVisitor;
+
+impl<'de> Visitor<'de> for BcdVisitor {
+    type Value = Bcd;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a string of BCD nibbles")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let nibbles = v.trim_ascii().iter().map(|c| match c {
+            b'0'..=b'9' => c - b'0',
+            b'F' | b'f' => 0xF,
+            _ => 0xA,
+        });
+        Bcd::from_nibbles(nibbles).ok_or(de::Error::custom("Invalid BCD nibbles"))
+    }
+}
+
+impl<'de> Deserialize<'de> for Bcd {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BcdVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bcd, BCD_MAX_BYTES};
+
+    #[test]
+    pub fn test_bcd_bytes_round_trip() {
+        let mut buf = [0; BCD_MAX_BYTES];
+        assert_eq!(Bcd(1234).to_bytes(&mut buf), &[0x12, 0x34]);
+        assert_eq!(Bcd(123).to_bytes(&mut buf), &[0x01, 0x23]);
+        assert_eq!(Bcd(0).to_bytes(&mut buf), &[0x00]);
+        assert_eq!(
+            Bcd(u64::MAX).to_bytes(&mut buf),
+            &[0x18, 0x44, 0x67, 0x44, 0x07, 0x37, 0x09, 0x55, 0x16, 0x15]
+        );
+
+        assert_eq!(Bcd::from_bytes(&[0x12, 0x34]), Some(Bcd(1234)));
+        assert_eq!(Bcd::from_bytes(&[0x01, 0x23]), Some(Bcd(123)));
+        assert_eq!(Bcd::from_bytes(&[0x1A]), None);
+        assert_eq!(Bcd::from_bytes(&[0x12, 0x3F]), Some(Bcd(123)));
+        assert_eq!(Bcd::from_bytes(&[0x1F, 0x23]), None);
+        assert_eq!(Bcd::from_bytes(&[0xFF]), None);
+        assert_eq!(Bcd::from_bytes(&[]), None);
+
+        for val in [0, 7, 42, 1234, 99999, u64::MAX] {
+            assert_eq!(Bcd::from_bytes(Bcd(val).to_bytes(&mut buf)), Some(Bcd(val)));
+        }
+    }
+
+    #[test]
+    pub fn test_parsing_bcd() {
+        let val: Bcd = crate::from_str("+CPBR: 1234").unwrap();
+        assert_eq!(*val, 1234);
+        let val: Bcd = crate::from_str("+CPBR: 0123").unwrap();
+        assert_eq!(*val, 123);
+        let val: Bcd = crate::from_str("+CPBR: 123F").unwrap();
+        assert_eq!(*val, 123);
+        assert!(crate::from_str::<Bcd>("+CPBR: 12F4").is_err());
+        assert!(crate::from_str::<Bcd>("+CPBR: ").is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! Parsing of colon separated lists within a single parameter.
+//!
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use serde::{de, Deserialize, Deserializer};
+
+/// Structure for parsing a parameter containing colon separated sub-values.
+///
+/// The surrounding parameters are still separated by commas, so only the
+/// single parameter is split on colons.
+///
+/// For example:
+///
+/// For the response: `+CMD: 1,2:3:4,5`
+///
+/// We can parse the second parameter as a `ColonList<u8, N>` which yields:
+/// `'2:3:4' => ColonList([2, 3, 4])`
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColonList<T, const N: usize>(pub heapless::Vec<T, N>);
+
+impl<T, const N: usize> Deref for ColonList<T, N> {
+    type Target = heapless::Vec<T, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de, T, const N: usize> Deserialize<'de> for ColonList<T, N>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(ColonListVisitor::<T, N>(PhantomData))
+    }
+}
+
+struct ColonListVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T, const N: usize> de::Visitor<'de> for ColonListVisitor<T, N>
+where
+    T: Deserialize<'de>,
+{
+    type Value = ColonList<T, N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("colon separated values, e.g.: \"2:3:4\"")
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let mut list = heapless::Vec::new();
+        for item in v.split(|&c| c == b':') {
+            let item = crate::from_slice(item).map_err(de::Error::custom)?;
+            list.push(item)
+                .map_err(|_| de::Error::invalid_length(N + 1, &self))?;
+        }
+        Ok(ColonList(list))
+    }
+}
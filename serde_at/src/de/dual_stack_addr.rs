@@ -0,0 +1,132 @@
+//! Parsing of dual-stack address pairs.
+//!
+use core::fmt;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use serde::{de, Deserialize, Deserializer};
+
+/// Structure for the addresses of a dual-stack PDP context, given as a quoted
+/// IPv4 address optionally followed by a quoted IPv6 address.
+///
+/// As all remaining input is consumed, this can only be used as the last
+/// parameter.
+///
+/// For example:
+///
+/// For the response: `+CGPADDR: 1,"10.0.0.2","2001:db8::1"`
+///
+/// We can parse the parameters as `(u8, DualStackAddr)` which yields `1` and
+/// `DualStackAddr { ipv4: Some(10.0.0.2), ipv6: Some(2001:db8::1) }`. For an
+/// IPv4-only context, eg. `+CGPADDR: 1,"10.0.0.2"`, `ipv6` is `None`, and for
+/// an IPv6-only context, eg. `+CGPADDR: 1,"","2001:db8::1"`, `ipv4` is `None`.
+///
+/// Serializes back to the same form, with an absent IPv4 address as `""`.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DualStackAddr {
+    /// The IPv4 address, if any
+    pub ipv4: Option<Ipv4Addr>,
+    /// The IPv6 address, if any
+    pub ipv6: Option<Ipv6Addr>,
+}
+
+impl<'de> Deserialize<'de> for DualStackAddr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // As with `LengthDelimited`, deserialize_tuple is used to get hold of
+        // the remaining input, as deserialize_bytes clips it at the first comma.
+        deserializer.deserialize_tuple(2, DualStackAddrVisitor)
+    }
+}
+
+struct DualStackAddrVisitor;
+
+/// Parse an optionally quoted address, which is absent if empty
+fn parse_addr<T: core::str::FromStr, E: de::Error>(v: &[u8]) -> Result<Option<T>, E> {
+    let v = v.trim_ascii();
+    let v = v
+        .strip_prefix(b"\"")
+        .and_then(|v| v.strip_suffix(b"\""))
+        .unwrap_or(v);
+    if v.is_empty() {
+        return Ok(None);
+    }
+    core::str::from_utf8(v)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Some)
+        .ok_or_else(|| de::Error::custom("Invalid IP address"))
+}
+
+impl<'de> de::Visitor<'de> for DualStackAddrVisitor {
+    type Value = DualStackAddr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an optional IPv4 address optionally followed by an IPv6 address")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let (ipv4, ipv6) = match v.iter().position(|&c| c == b',') {
+            Some(i) => (&v[..i], Some(&v[i + 1..])),
+            None => (v, None),
+        };
+
+        Ok(DualStackAddr {
+            ipv4: parse_addr(ipv4)?,
+            ipv6: ipv6.map(parse_addr).transpose()?.flatten(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DualStackAddr;
+    use core::net::{Ipv4Addr, Ipv6Addr};
+    use serde_derive::Deserialize;
+
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    struct PdpAddress {
+        cid: u8,
+        addr: DualStackAddr,
+    }
+
+    #[test]
+    fn dual_stack_addr() {
+        assert_eq!(
+            crate::from_str("+CGPADDR: 1,\"10.0.0.2\",\"2001:db8::1\""),
+            Ok(PdpAddress {
+                cid: 1,
+                addr: DualStackAddr {
+                    ipv4: Some(Ipv4Addr::new(10, 0, 0, 2)),
+                    ipv6: Some(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+                }
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CGPADDR: 1,\"10.0.0.2\""),
+            Ok(PdpAddress {
+                cid: 1,
+                addr: DualStackAddr {
+                    ipv4: Some(Ipv4Addr::new(10, 0, 0, 2)),
+                    ipv6: None,
+                }
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CGPADDR: 1,\"\",\"2001:db8::1\""),
+            Ok(PdpAddress {
+                cid: 1,
+                addr: DualStackAddr {
+                    ipv4: None,
+                    ipv6: Some(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+                }
+            })
+        );
+        assert!(crate::from_str::<PdpAddress>("+CGPADDR: 1,\"10.0.0\"").is_err());
+    }
+}
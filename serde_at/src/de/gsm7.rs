@@ -0,0 +1,168 @@
+//! Parsing of GSM 7-bit packed text.
+//!
+use core::fmt;
+use core::ops::Deref;
+
+use heapless::String;
+use serde::{de, Deserialize, Deserializer};
+
+/// Escape to the GSM 7-bit extension table
+const ESC: u8 = 0x1B;
+
+/// GSM 03.38 default alphabet. The escape code is never looked up.
+#[rustfmt::skip]
+const BASIC: [char; 128] = [
+    '@', '£', '$', '¥', 'è', 'é', 'ù', 'ì', 'ò', 'Ç', '\n', 'Ø', 'ø', '\r', 'Å', 'å',
+    'Δ', '_', 'Φ', 'Γ', 'Λ', 'Ω', 'Π', 'Ψ', 'Σ', 'Θ', 'Ξ', ' ', 'Æ', 'æ', 'ß', 'É',
+    ' ', '!', '"', '#', '¤', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?',
+    '¡', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'Ä', 'Ö', 'Ñ', 'Ü', '§',
+    '¿', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'ä', 'ö', 'ñ', 'ü', 'à',
+];
+
+/// GSM 03.38 extension table, for the septet following an escape.
+fn extension(septet: u8) -> Option<char> {
+    Some(match septet {
+        0x0A => '\x0C',
+        0x14 => '^',
+        0x28 => '{',
+        0x29 => '}',
+        0x2F => '\\',
+        0x3C => '[',
+        0x3D => '~',
+        0x3E => ']',
+        0x40 => '|',
+        0x65 => '€',
+        _ => return None,
+    })
+}
+
+/// Structure for parsing hex encoded GSM 7-bit packed text, as found in the
+/// user data of SMS read in PDU mode, into a UTF-8 string.
+///
+/// Septets of the GSM default alphabet and its extension table are decoded.
+/// When the text fills all but 7 bits of the last octet, the padding is
+/// indistinguishable from a trailing `@`, and is dropped.
+///
+/// For example:
+///
+/// For the response: `+CMD: "E8329BFD4697D9EC37"`
+///
+/// We can parse the parameter as a `Gsm7<16>` which yields:
+/// `Gsm7("hellohello")`
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gsm7<const N: usize>(pub String<N>);
+
+impl<const N: usize> Deref for Gsm7<N> {
+    type Target = String<N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Gsm7<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(Gsm7Visitor::<N>)
+    }
+}
+
+struct Gsm7Visitor<const N: usize>;
+
+fn hex_byte(hi: u8, lo: u8) -> Option<u8> {
+    let nibble = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+    Some((nibble(hi)? << 4) | nibble(lo)?)
+}
+
+impl<'de, const N: usize> de::Visitor<'de> for Gsm7Visitor<N> {
+    type Value = Gsm7<N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("hex encoded GSM 7-bit packed text, e.g.: \"E8329BFD4697D9EC37\"")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let v = v.trim_ascii();
+        let v = v
+            .strip_prefix(b"\"")
+            .and_then(|v| v.strip_suffix(b"\""))
+            .unwrap_or(v);
+        if v.len() % 2 != 0 {
+            return Err(de::Error::custom("Invalid hex encoding"));
+        }
+
+        let octet = |i: usize| hex_byte(v[2 * i], v[2 * i + 1]);
+        let octets = v.len() / 2;
+        let mut septets = octets * 8 / 7;
+
+        let septet = |i: usize| -> Option<u8> {
+            let bit = i * 7;
+            let (index, shift) = (bit / 8, bit % 8);
+            let mut value = octet(index)? >> shift;
+            if shift > 1 {
+                value |= octet(index + 1)? << (8 - shift);
+            }
+            Some(value & 0x7F)
+        };
+
+        if octets % 7 == 0 && septets > 0 && septet(septets - 1) == Some(0) {
+            septets -= 1;
+        }
+
+        let mut text = String::new();
+        let mut escaped = false;
+        for i in 0..septets {
+            let s = septet(i).ok_or_else(|| de::Error::custom("Invalid hex encoding"))?;
+            let c = if escaped {
+                escaped = false;
+                // Unknown extensions fall back to the default alphabet
+                extension(s).unwrap_or(BASIC[usize::from(s)])
+            } else if s == ESC {
+                escaped = true;
+                continue;
+            } else {
+                BASIC[usize::from(s)]
+            };
+            text.push(c)
+                .map_err(|_| de::Error::invalid_length(text.len() + 1, &self))?;
+        }
+        Ok(Gsm7(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gsm7;
+    use serde_derive::Deserialize;
+
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    struct WithText {
+        index: u8,
+        text: Gsm7<16>,
+    }
+
+    #[test]
+    fn gsm7() {
+        let res: WithText = crate::from_str("+CMD: 1,\"E8329BFD4697D9EC37\"").unwrap();
+        assert_eq!(res.text.as_str(), "hellohello");
+
+        // Extension table
+        let res: WithText = crate::from_str("+CMD: 1,C8346853AE01").unwrap();
+        assert_eq!(res.text.as_str(), "Hi €5");
+
+        // Padding filling the last octet
+        let res: WithText = crate::from_str("+CMD: 1,61F1985C369F01").unwrap();
+        assert_eq!(res.text.as_str(), "abcdefg");
+
+        assert!(crate::from_str::<WithText>("+CMD: 1,E8329").is_err());
+    }
+}
@@ -27,6 +27,9 @@ pub struct HexStr<T> {
     pub delimiter: char,
     /// Skip last 0 values. Whether or not to include 0 values
     pub skip_last_0_values: bool,
+    /// Flag to keep capital letters regardless of
+    /// [`SerializeOptions::hex_caps`](crate::SerializeOptions::hex_caps)
+    pub fixed_case: bool,
 }
 
 impl<T> Default for HexStr<T>
@@ -41,6 +44,7 @@ where
             delimiter_after_nibble_count: 0,
             delimiter: ' ',
             skip_last_0_values: true,
+            fixed_case: false,
         }
     }
 }
@@ -108,6 +112,49 @@ macro_rules! impl_hex_literal_visitor {
 
 impl_hex_literal_visitor! { u8 u16 u32 u64 u128 }
 
+/// Signed values are parsed as their unsigned counterpart, and reinterpreted as
+/// two's complement, eg. `FFFFFFFF` => `-1i32`
+macro_rules! impl_signed_hex_literal_visitor {
+    ($($int_type:ty => $uint_type:ty)*) => {$(
+        impl<'de> Visitor<'de> for HexLiteralVisitor<$int_type> {
+            type Value = $int_type;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a two's complement integer in hexadecimal notation")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                HexLiteralVisitor::<$uint_type> { _ty: PhantomData }
+                    .visit_bytes(v)
+                    .map(|v| v as $int_type)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for HexStr<$int_type> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+            {
+                let val = deserializer.deserialize_bytes(HexLiteralVisitor::<$int_type> { _ty: PhantomData })?;
+                Ok(HexStr { val, ..Default::default() })
+            }
+        }
+
+        impl Deref for HexStr<$int_type> {
+            type Target = $int_type;
+
+            fn deref(&self) -> &Self::Target {
+                &self.val
+            }
+        }
+    )*}
+}
+
+impl_signed_hex_literal_visitor! { i8 => u8 i16 => u16 i32 => u32 i64 => u64 i128 => u128 }
+
 #[cfg(feature = "hex_str_arrays")]
 mod unstable {
     use crate::de::hex_str::HexLiteralVisitor;
@@ -182,6 +229,7 @@ mod unstable {
                 delimiter_after_nibble_count: 0,
                 delimiter: ' ',
                 skip_last_0_values: false,
+                fixed_case: false,
             })
         }
     }
@@ -221,6 +269,16 @@ mod tests {
         assert_eq!(*val, 0x1234567890abcdef1234567890abcdef);
     }
 
+    #[test]
+    pub fn test_parsing_a_signed_hex_string() {
+        let val: HexStr<i32> = crate::from_str("+REG: 0xFFFFFFFF").unwrap();
+        assert_eq!(*val, -1);
+        let val: HexStr<i32> = crate::from_str("+REG: 7FFFFFFF").unwrap();
+        assert_eq!(*val, i32::MAX);
+        let val: HexStr<i64> = crate::from_str("+REG: FFFFFFFFFFFFFF85").unwrap();
+        assert_eq!(*val, -123);
+    }
+
     #[cfg(feature = "hex_str_arrays")]
     #[test]
     pub fn test_hex_str_arrays() {
@@ -0,0 +1,156 @@
+//! Parsing of keyword events followed by numeric arguments.
+//!
+use core::fmt;
+
+use heapless::{String, Vec};
+use serde::{de, Deserialize, Deserializer};
+
+/// Structure for parsing an event given as a keyword of one or more words,
+/// optionally followed by numeric arguments, such as the `+CGEV` URCs.
+///
+/// The keyword is made up of the leading words, joined by single spaces. Any
+/// following arguments must be numeric, separated by spaces or commas. As all
+/// remaining input is consumed, this can only be used as the last parameter.
+///
+/// The keyword and arguments are typically matched on to dispatch to the
+/// variants of an enum, from its `Deserialize` implementation.
+///
+/// For example:
+///
+/// For the response: `+CGEV: ME PDN ACT 1`
+///
+/// We can parse the parameters as a `KeywordArgs<16, 4>` which yields:
+/// `KeywordArgs { keyword: "ME PDN ACT", args: [1] }`
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeywordArgs<const K: usize, const N: usize> {
+    /// The keyword
+    pub keyword: String<K>,
+    /// The numeric arguments following the keyword
+    pub args: Vec<u32, N>,
+}
+
+impl<'de, const K: usize, const N: usize> Deserialize<'de> for KeywordArgs<K, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // As with `LengthDelimited`, deserialize_tuple is used to get hold of
+        // the remaining input, as deserialize_bytes clips it at the first comma.
+        deserializer.deserialize_tuple(N + 1, KeywordArgsVisitor::<K, N>)
+    }
+}
+
+struct KeywordArgsVisitor<const K: usize, const N: usize>;
+
+impl<'de, const K: usize, const N: usize> de::Visitor<'de> for KeywordArgsVisitor<K, N> {
+    type Value = KeywordArgs<K, N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a keyword followed by numeric arguments, e.g.: \"ME PDN ACT 1\"")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let v = core::str::from_utf8(v).map_err(|_| de::Error::custom("Invalid UTF-8"))?;
+
+        let mut keyword = String::new();
+        let mut args = Vec::new();
+        for token in v.split([' ', ',']).map(str::trim).filter(|t| !t.is_empty()) {
+            if let Ok(arg) = token.parse::<u32>() {
+                args.push(arg)
+                    .map_err(|_| de::Error::invalid_length(args.len() + 1, &self))?;
+            } else if args.is_empty() {
+                if !keyword.is_empty() {
+                    keyword
+                        .push(' ')
+                        .map_err(|_| de::Error::custom("Keyword too long"))?;
+                }
+                keyword
+                    .push_str(token)
+                    .map_err(|_| de::Error::custom("Keyword too long"))?;
+            } else {
+                return Err(de::Error::custom("Non-numeric argument"));
+            }
+        }
+
+        if keyword.is_empty() {
+            return Err(de::Error::custom("Missing keyword"));
+        }
+        Ok(KeywordArgs { keyword, args })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeywordArgs;
+    use serde::{de, Deserialize as _, Deserializer};
+    use serde_derive::Deserialize;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum PacketDomainEvent {
+        NetworkDetach,
+        PdnActivated(u8),
+        PdnDeactivated(u8),
+        Modified { cid: u8, change_reason: u8 },
+    }
+
+    impl<'de> serde::Deserialize<'de> for PacketDomainEvent {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let event = KeywordArgs::<16, 3>::deserialize(deserializer)?;
+            let arg = |i: usize| event.args[i] as u8;
+            match (event.keyword.as_str(), event.args.len()) {
+                ("NW DETACH" | "ME DETACH", 0) => Ok(Self::NetworkDetach),
+                ("ME PDN ACT" | "NW PDN ACT", 1) => Ok(Self::PdnActivated(arg(0))),
+                ("ME PDN DEACT" | "NW PDN DEACT", 1) => Ok(Self::PdnDeactivated(arg(0))),
+                ("NW MODIFY" | "ME MODIFY", 3) => Ok(Self::Modified {
+                    cid: arg(0),
+                    change_reason: arg(1),
+                }),
+                _ => Err(de::Error::custom("Unknown +CGEV event")),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    struct Cgev {
+        event: PacketDomainEvent,
+    }
+
+    #[test]
+    fn keyword_args() {
+        assert_eq!(
+            crate::from_str::<Cgev>("+CGEV: NW DETACH").unwrap().event,
+            PacketDomainEvent::NetworkDetach
+        );
+        assert_eq!(
+            crate::from_str::<Cgev>("+CGEV: ME PDN ACT 1")
+                .unwrap()
+                .event,
+            PacketDomainEvent::PdnActivated(1)
+        );
+        assert_eq!(
+            crate::from_str::<Cgev>("+CGEV: NW PDN DEACT 2")
+                .unwrap()
+                .event,
+            PacketDomainEvent::PdnDeactivated(2)
+        );
+        assert_eq!(
+            crate::from_str::<Cgev>("+CGEV: NW MODIFY 1,2,0")
+                .unwrap()
+                .event,
+            PacketDomainEvent::Modified {
+                cid: 1,
+                change_reason: 2
+            }
+        );
+
+        assert!(crate::from_str::<Cgev>("+CGEV: NW CLASS 1").is_err());
+        assert!(crate::from_str::<Cgev>("+CGEV: ME PDN ACT 1,x").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! Parsing of trailing key-value pairs.
+//!
+use core::fmt;
+use core::ops::Deref;
+
+use heapless::{String, Vec};
+use serde::{de, Deserialize, Deserializer};
+
+/// Structure for parsing trailing `key=value` pairs, as returned by some
+/// diagnostic commands.
+///
+/// Values may be quoted, in which case the quotes are stripped and the value
+/// may contain commas. As all remaining input is consumed, this can only be
+/// used as the last parameter.
+///
+/// For example:
+///
+/// For the response: `+CMD: key1=val1,key2="val 2"`
+///
+/// We can parse the parameters as a `KvList<N, M>` which yields:
+/// `KvList([("key1", "val1"), ("key2", "val 2")])`
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KvList<const N: usize, const M: usize>(pub Vec<(String<M>, String<M>), N>);
+
+impl<const N: usize, const M: usize> Deref for KvList<N, M> {
+    type Target = Vec<(String<M>, String<M>), N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de, const N: usize, const M: usize> Deserialize<'de> for KvList<N, M> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // As with `LengthDelimited`, deserialize_tuple is used to get hold of
+        // the remaining input, as deserialize_bytes clips it at the first comma.
+        deserializer.deserialize_tuple(N, KvListVisitor::<N, M>)
+    }
+}
+
+struct KvListVisitor<const N: usize, const M: usize>;
+
+fn to_string<const M: usize, E: de::Error>(v: &[u8]) -> Result<String<M>, E> {
+    let v = v.trim_ascii();
+    let v = v
+        .strip_prefix(b"\"")
+        .and_then(|v| v.strip_suffix(b"\""))
+        .unwrap_or(v);
+    let v = core::str::from_utf8(v).map_err(|_| de::Error::custom("Invalid UTF-8"))?;
+    String::try_from(v).map_err(|_| de::Error::invalid_length(v.len(), &"a shorter string"))
+}
+
+impl<'de, const N: usize, const M: usize> de::Visitor<'de> for KvListVisitor<N, M> {
+    type Value = KvList<N, M>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("key-value pairs, e.g.: \"key1=val1,key2=val2\"")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let mut list = Vec::new();
+        let mut in_quotes = false;
+        let pairs = v.split(|&c| match c {
+            b'"' => {
+                in_quotes = !in_quotes;
+                false
+            }
+            b',' => !in_quotes,
+            _ => false,
+        });
+
+        for pair in pairs.filter(|p| !p.trim_ascii().is_empty()) {
+            let i = pair
+                .iter()
+                .position(|&c| c == b'=')
+                .ok_or_else(|| de::Error::custom("Missing '=' in key-value pair"))?;
+            let entry = (to_string(&pair[..i])?, to_string(&pair[i + 1..])?);
+            list.push(entry)
+                .map_err(|_| de::Error::invalid_length(N + 1, &self))?;
+        }
+        Ok(KvList(list))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KvList;
+    use heapless::String;
+    use serde_derive::Deserialize;
+
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    struct Diagnostics {
+        stats: KvList<4, 16>,
+    }
+
+    #[test]
+    fn kv_list() {
+        let res: Diagnostics = crate::from_str("+CMD: key1=val1,key2=\"val,2\"").unwrap();
+        assert_eq!(
+            res.stats.as_slice(),
+            &[
+                (
+                    String::<16>::try_from("key1").unwrap(),
+                    String::<16>::try_from("val1").unwrap()
+                ),
+                (
+                    String::<16>::try_from("key2").unwrap(),
+                    String::<16>::try_from("val,2").unwrap()
+                ),
+            ]
+        );
+
+        assert!(crate::from_str::<Diagnostics>("+CMD: key1").is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use core::fmt;
+use core::ops::Deref;
+use serde::de::Visitor;
+use serde::{de, Deserialize};
+
+/// `MacAddr`
+/// A MAC address, written as six colon-separated hex octets, eg.
+/// `"01:23:45:67:89:AB"`. Responses are accepted quoted or unquoted, in any
+/// case. Serialized as a quoted upper case string.
+/// Can be dereferenced to its octets.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl Deref for MacAddr {
+    type Target = [u8; 6];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<[u8; 6]> for MacAddr {
+    fn from(value: [u8; 6]) -> Self {
+        Self(value)
+    }
+}
+
+fn hex_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+struct MacAddrVisitor;
+
+impl<'de> Visitor<'de> for MacAddrVisitor {
+    type Value = MacAddr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a MAC address, e.g.: \"01:23:45:67:89:AB\"")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let v = v.trim_ascii();
+        let v = v
+            .strip_prefix(b"\"")
+            .and_then(|v| v.strip_suffix(b"\""))
+            .unwrap_or(v);
+
+        let mut octets = [0; 6];
+        let mut parts = v.split(|&c| c == b':');
+        for octet in octets.iter_mut() {
+            *octet = match parts.next() {
+                Some(&[hi, lo]) => match (hex_nibble(hi), hex_nibble(lo)) {
+                    (Some(hi), Some(lo)) => (hi << 4) | lo,
+                    _ => return Err(de::Error::custom("Invalid MAC address")),
+                },
+                _ => return Err(de::Error::custom("Invalid MAC address")),
+            };
+        }
+
+        if parts.next().is_some() {
+            return Err(de::Error::custom("Invalid MAC address"));
+        }
+
+        Ok(MacAddr(octets))
+    }
+}
+
+impl<'de> Deserialize<'de> for MacAddr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(MacAddrVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MacAddr;
+
+    #[test]
+    pub fn test_parsing_mac_addr() {
+        let expected = MacAddr([0x01, 0x23, 0x45, 0x67, 0x89, 0xAB]);
+        for input in [
+            "+UWAPMACADDR: \"01:23:45:67:89:AB\"",
+            "+UWAPMACADDR: \"01:23:45:67:89:ab\"",
+            "+UWAPMACADDR: 01:23:45:67:89:AB",
+        ] {
+            assert_eq!(crate::from_str::<MacAddr>(input), Ok(expected), "{input}");
+        }
+        assert!(crate::from_str::<MacAddr>("+UWAPMACADDR: \"01:23:45:67:89\"").is_err());
+        assert!(crate::from_str::<MacAddr>("+UWAPMACADDR: \"01:23:45:67:89:AB:CD\"").is_err());
+        assert!(crate::from_str::<MacAddr>("+UWAPMACADDR: \"01:23:45:67:89:AG\"").is_err());
+    }
+}
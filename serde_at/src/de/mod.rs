@@ -8,19 +8,45 @@ use serde::de::{self, Visitor};
 use self::enum_::VariantAccess;
 use self::map::MapAccess;
 use self::seq::SeqAccess;
+use self::trailing_discriminant::{TrailingAccess, TrailingVariantAccess};
 
 #[cfg(feature = "log")]
 use log;
 
+#[cfg(feature = "heapless")]
+pub mod colon_list;
+pub mod dual_stack_addr;
 mod enum_;
 #[cfg(feature = "heapless")]
+pub mod gsm7;
+#[cfg(feature = "heapless")]
+pub mod keyword_args;
+#[cfg(feature = "heapless")]
+pub mod kv_list;
+#[cfg(feature = "heapless")]
 pub mod length_delimited;
 mod map;
+#[cfg(feature = "heapless")]
+pub mod multi_line;
+#[cfg(feature = "heapless")]
+pub mod percent_decoded;
+pub mod sentinel;
 mod seq;
+pub mod tracked;
+pub mod trailing_discriminant;
 
 /// Hex string helper module
 pub mod hex_str;
 
+/// BCD helper module
+pub mod bcd;
+
+/// On/off toggle helper module
+pub mod on_off;
+
+/// MAC address helper module
+pub mod mac_addr;
+
 /// Deserialization result
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -53,6 +79,19 @@ pub enum Error {
     /// Invalid type
     InvalidType,
 
+    /// Value of the given length does not fit the target type, eg. a string
+    /// longer than the capacity of a `heapless::String<N>`, or a response with
+    /// fewer fields than the target struct.
+    InvalidLength {
+        /// Length of the value
+        len: usize,
+        /// Length expected by the target type, eg. the capacity of a
+        /// `heapless::String<N>`, if stated by it
+        expected: Option<usize>,
+        /// Position of the parameter holding the value, if known
+        field: Option<usize>,
+    },
+
     /// Invalid unicode code point.
     InvalidUnicodeCodePoint,
 
@@ -70,20 +109,72 @@ pub enum Error {
     CustomErrorWithMessage(heapless::String<128>),
 }
 
+/// Options used by the deserializer, to accept non-standard response formats
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DeserializeOptions {
+    /// Whether to accept a response identifier delimited by a space instead of
+    /// a colon, eg. `+CMD 1,2`
+    ///
+    /// **default**: false
+    pub space_delimited_token: bool,
+    /// Whether to accept a response identifier delimited by `=` instead of a
+    /// colon, eg. `+CMD=1,2`, as echoed by some non-compliant modems
+    ///
+    /// **default**: false
+    pub equals_delimited_token: bool,
+    /// Decimal separator used by floating point values instead of `.`, eg.
+    /// `b','` to parse `+CMD: 1,5` as `1.5`. At most one separator followed
+    /// by a digit is consumed, so that any following parameters are still
+    /// separated.
+    ///
+    /// **default**: None
+    pub decimal_separator: Option<u8>,
+    /// Whether to accept line breaks as separators between fields, in addition
+    /// to commas, eg. `+CMD:\r\n1\r\n"abc"\r\n`
+    ///
+    /// **default**: false
+    pub newline_separated_fields: bool,
+    /// Whether to accept quoted integers containing `,` as thousands
+    /// separators, eg. `+CMD: "1,234"` parsed as `1234`. Unquoted integers are
+    /// unaffected, as the `,` separates them from the following field.
+    ///
+    /// **default**: false
+    pub quoted_grouped_numbers: bool,
+}
+
 pub(crate) struct Deserializer<'a> {
     slice: &'a [u8],
     index: usize,
     struct_size_hint: Option<usize>,
     is_trailing_parsing: bool,
+    /// Layout of the next enum, if it has a trailing discriminant, see
+    /// [`TrailingDiscriminant`](trailing_discriminant::TrailingDiscriminant)
+    trailing_discriminant: Option<trailing_discriminant::Layout>,
+    /// Whether the next sequence records its present parameters, see
+    /// [`Tracked`](tracked::Tracked)
+    track_fields: bool,
+    /// Bitmask of the parameters present in the tracked sequence
+    present: u32,
+    options: DeserializeOptions,
+}
+
+/// Whether `c` is a known prefix of AT command identifiers, eg. `+` in
+/// `+CSQ` or `#` in Telit's `#SGACT`
+pub(crate) const fn is_at_prefix(c: u8) -> bool {
+    matches!(c, b'+' | b'#' | b'$' | b'&' | b'%')
 }
 
 impl<'a> Deserializer<'a> {
-    const fn new(slice: &'a [u8]) -> Self {
+    const fn new(slice: &'a [u8], options: DeserializeOptions) -> Self {
         Self {
             slice,
             index: 0,
             struct_size_hint: None,
             is_trailing_parsing: false,
+            trailing_discriminant: None,
+            track_fields: false,
+            present: 0,
+            options,
         }
     }
 
@@ -193,13 +284,20 @@ impl<'a> Deserializer<'a> {
     }
 
     fn parse_at(&mut self) -> Result<Option<()>> {
-        // match AT command identifier starting in known prefixes and ending in ':'
-        if self
-            .parse_whitespace()
-            .map(|c| matches!(c, b'+' | b'#' | b'$' | b'&' | b'%'))
-            == Some(true)
+        // match AT command identifier starting in known prefixes and ending in
+        // ':', or optionally ' '
+        let is_prefix = |c: Option<&u8>| c.is_some_and(|&c| is_at_prefix(c));
+
+        self.parse_whitespace();
+        let index = self.index;
+
+        // Tolerate a leading (echoed) `AT` before the identifier
+        if self.slice[self.index..].starts_with(b"AT") && is_prefix(self.slice.get(self.index + 2))
         {
-            let index = self.index;
+            self.index += 2;
+        }
+
+        if is_prefix(self.slice.get(self.index)) {
             loop {
                 match self.peek() {
                     Some(b':') => {
@@ -207,20 +305,75 @@ impl<'a> Deserializer<'a> {
                         self.parse_whitespace().ok_or(Error::EofWhileParsingValue)?;
                         return Ok(Some(()));
                     }
+                    Some(b' ') if self.options.space_delimited_token => {
+                        self.parse_whitespace().ok_or(Error::EofWhileParsingValue)?;
+                        return Ok(Some(()));
+                    }
+                    Some(b'=') if self.options.equals_delimited_token => {
+                        self.eat_char();
+                        self.parse_whitespace().ok_or(Error::EofWhileParsingValue)?;
+                        return Ok(Some(()));
+                    }
                     Some(_) => {
                         self.eat_char();
                     }
                     None => {
                         // Doesn't seem to be an AT command identifier. Reset index and continue
-                        self.index = index;
                         break;
                     }
                 }
             }
         }
+        self.index = index;
         Ok(None)
     }
 
+    /// Parse a floating point value using a custom decimal separator
+    fn parse_float_with_separator<T: str::FromStr>(&mut self, separator: u8) -> Result<T> {
+        let mut buf = [0; 64];
+        let mut len = 0;
+        let mut seen_separator = false;
+        while let Some(c) = self.peek() {
+            let c = match c {
+                b'0'..=b'9' | b'+' | b'-' | b'e' | b'E' => c,
+                c if c == separator
+                    && !seen_separator
+                    && self
+                        .slice
+                        .get(self.index + 1)
+                        .is_some_and(u8::is_ascii_digit) =>
+                {
+                    seen_separator = true;
+                    b'.'
+                }
+                _ => break,
+            };
+            *buf.get_mut(len).ok_or(Error::InvalidNumber)? = c;
+            len += 1;
+            self.eat_char();
+        }
+        let s = str::from_utf8(&buf[..len]).map_err(|_| Error::InvalidNumber)?;
+        T::from_str(s).map_err(|_| Error::InvalidNumber)
+    }
+
+    /// Consumes any spaces followed by a single line break, returning whether a
+    /// line break was found. Nothing is consumed if no line break follows.
+    fn parse_line_break(&mut self) -> bool {
+        let index = self.index;
+        while let Some(b' ' | b'\t') = self.peek() {
+            self.eat_char();
+        }
+        if self.peek() == Some(b'\r') {
+            self.eat_char();
+        }
+        if self.peek() == Some(b'\n') {
+            self.eat_char();
+            return true;
+        }
+        self.index = index;
+        false
+    }
+
     /// Consumes all the whitespace characters and returns a peek into the next character
     fn parse_whitespace(&mut self) -> Option<u8> {
         loop {
@@ -238,6 +391,28 @@ impl<'a> Deserializer<'a> {
     fn peek(&mut self) -> Option<u8> {
         self.slice.get(self.index).copied()
     }
+
+    /// Consume the opening quote of an integer with thousands separators, if
+    /// enabled through `DeserializeOptions::quoted_grouped_numbers`.
+    fn parse_number_quote(&mut self) -> bool {
+        if self.options.quoted_grouped_numbers && self.parse_whitespace() == Some(b'"') {
+            self.eat_char();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume the closing quote of an integer opened by `parse_number_quote`
+    fn end_number_quote(&mut self, quoted: bool) -> Result<()> {
+        if quoted {
+            if self.peek() != Some(b'"') {
+                return Err(Error::InvalidNumber);
+            }
+            self.eat_char();
+        }
+        Ok(())
+    }
 }
 
 // NOTE(deserialize_*signed) we avoid parsing into u64 and then casting to a smaller integer, which
@@ -245,6 +420,7 @@ impl<'a> Deserializer<'a> {
 // Flash, when targeting non 64-bit architectures
 macro_rules! deserialize_unsigned {
     ($self:ident, $visitor:ident, $uxx:ident, $visit_uxx:ident) => {{
+        let quoted = $self.parse_number_quote();
         let peek = $self
             .parse_whitespace()
             .ok_or(Error::EofWhileParsingValue)?;
@@ -265,7 +441,11 @@ macro_rules! deserialize_unsigned {
                                 .checked_add((c - b'0') as $uxx)
                                 .ok_or(Error::InvalidNumber)?;
                         }
-                        _ => return $visitor.$visit_uxx(number),
+                        Some(b',') if quoted => $self.eat_char(),
+                        _ => {
+                            $self.end_number_quote(quoted)?;
+                            return $visitor.$visit_uxx(number);
+                        }
                     }
                 }
             }
@@ -276,6 +456,7 @@ macro_rules! deserialize_unsigned {
 
 macro_rules! deserialize_signed {
     ($self:ident, $visitor:ident, $ixx:ident, $visit_ixx:ident) => {{
+        let quoted = $self.parse_number_quote();
         let signed = match $self
             .parse_whitespace()
             .ok_or(Error::EofWhileParsingValue)?
@@ -302,7 +483,11 @@ macro_rules! deserialize_signed {
                                 .checked_add((c - b'0') as $ixx * if signed { -1 } else { 1 })
                                 .ok_or(Error::InvalidNumber)?;
                         }
-                        _ => return $visitor.$visit_ixx(number),
+                        Some(b',') if quoted => $self.eat_char(),
+                        _ => {
+                            $self.end_number_quote(quoted)?;
+                            return $visitor.$visit_ixx(number);
+                        }
                     }
                 }
             }
@@ -440,6 +625,9 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
         V: Visitor<'de>,
     {
         self.parse_whitespace().ok_or(Error::EofWhileParsingValue)?;
+        if let Some(separator) = self.options.decimal_separator {
+            return visitor.visit_f32(self.parse_float_with_separator(separator)?);
+        }
         deserialize_fromstr!(self, visitor, f32, visit_f32, b"0123456789+-.eE")
     }
 
@@ -448,6 +636,9 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
         V: Visitor<'de>,
     {
         self.parse_whitespace().ok_or(Error::EofWhileParsingValue)?;
+        if let Some(separator) = self.options.decimal_separator {
+            return visitor.visit_f64(self.parse_float_with_separator(separator)?);
+        }
         deserialize_fromstr!(self, visitor, f64, visit_f64, b"0123456789+-.eE")
     }
 
@@ -554,11 +745,20 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
         unreachable!()
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        if name == tracked::TRACKED {
+            self.track_fields = true;
+            self.present = 0;
+            return visitor.visit_seq(tracked::TrackedAccess::new(self));
+        }
+
         self.parse_at()?;
+        if name == trailing_discriminant::TRAILING_DISCRIMINANT {
+            return visitor.visit_seq(TrailingAccess::new(self));
+        }
         visitor.visit_newtype_struct(self)
     }
 
@@ -583,17 +783,22 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
             })
     }
 
-    /// Unsupported
     fn deserialize_tuple_struct<V>(
         self,
         _name: &'static str,
-        _len: usize,
-        _visitor: V,
+        len: usize,
+        visitor: V,
     ) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        unreachable!()
+        self.parse_at()?;
+
+        self.struct_size_hint = Some(len);
+        let result = self.deserialize_seq(visitor);
+        self.struct_size_hint = None;
+
+        result
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
@@ -640,6 +845,9 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
         V: Visitor<'de>,
     {
         self.parse_whitespace().ok_or(Error::EofWhileParsingValue)?;
+        if let Some(layout) = self.trailing_discriminant.take() {
+            return visitor.visit_enum(TrailingVariantAccess::new(self, layout));
+        }
         visitor.visit_enum(VariantAccess::new(self))
     }
 
@@ -647,7 +855,16 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        self.deserialize_str(visitor)
+        if self.parse_whitespace().ok_or(Error::EofWhileParsingValue)? == b'"' {
+            return self.deserialize_str(visitor);
+        }
+
+        // Unquoted identifiers, eg. enum variants, end at the next parameter
+        let start = self.index;
+        while !matches!(self.peek(), None | Some(b',')) {
+            self.eat_char();
+        }
+        visitor.visit_borrowed_bytes(&self.slice[start..self.index])
     }
 
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
@@ -670,6 +887,31 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
     }
 }
 
+/// Captures the first number written, eg. the capacity `8` from the
+/// expectation "a string no more than 8 bytes long"
+#[derive(Default)]
+struct FirstNumber {
+    number: Option<usize>,
+    done: bool,
+}
+
+impl fmt::Write for FirstNumber {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.bytes() {
+            if self.done {
+                break;
+            }
+            if c.is_ascii_digit() {
+                let n = self.number.unwrap_or(0);
+                self.number = Some(n.saturating_mul(10).saturating_add((c - b'0') as usize));
+            } else if self.number.is_some() {
+                self.done = true;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl de::Error for Error {
     #[cfg_attr(not(feature = "custom-error-messages"), allow(unused_variables))]
     fn custom<T>(msg: T) -> Self
@@ -689,6 +931,27 @@ impl de::Error for Error {
             Self::CustomErrorWithMessage(string)
         }
     }
+
+    fn invalid_length(len: usize, exp: &dyn de::Expected) -> Self {
+        use core::fmt::Write;
+
+        let mut expected = FirstNumber::default();
+        let _ = write!(expected, "{}", exp);
+        Self::InvalidLength {
+            len,
+            expected: expected.number,
+            field: None,
+        }
+    }
+
+    /// Integers rejected by the target type (eg. zero for `NonZeroU8`) are
+    /// reported as [`Error::InvalidNumber`].
+    fn invalid_value(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        match unexp {
+            de::Unexpected::Unsigned(_) | de::Unexpected::Signed(_) => Self::InvalidNumber,
+            _ => Self::custom(format_args!("invalid value: {}, expected {}", unexp, exp)),
+        }
+    }
 }
 
 impl de::StdError for Error {}
@@ -709,6 +972,7 @@ impl fmt::Display for Error {
                 Self::ExpectedSomeValue => "Expected this character to start an AT Command string.",
                 Self::InvalidNumber => "Invalid number.",
                 Self::InvalidType => "Invalid type",
+                Self::InvalidLength { .. } => "Invalid length of value.",
                 Self::InvalidUnicodeCodePoint => "Invalid unicode code point.",
                 Self::TrailingCharacters => {
                     "AT Command string has non-whitespace trailing characters after \
@@ -730,18 +994,27 @@ pub fn from_slice<'a, T>(v: &'a [u8]) -> Result<T>
 where
     T: de::Deserialize<'a>,
 {
-    fn _from_slice<'a, T>(v: &'a [u8]) -> Result<T>
+    from_slice_with_options(v, DeserializeOptions::default())
+}
+
+/// Deserializes an instance of type `T` from bytes of AT Response text, using
+/// the given [`DeserializeOptions`]
+pub fn from_slice_with_options<'a, T>(v: &'a [u8], options: DeserializeOptions) -> Result<T>
+where
+    T: de::Deserialize<'a>,
+{
+    fn _from_slice<'a, T>(v: &'a [u8], options: DeserializeOptions) -> Result<T>
     where
         T: de::Deserialize<'a>,
     {
-        let mut de = Deserializer::new(v.trim_ascii());
+        let mut de = Deserializer::new(v.trim_ascii(), options);
         let value = de::Deserialize::deserialize(&mut de)?;
         de.end()?;
         Ok(value)
     }
 
     #[allow(clippy::map_identity)]
-    _from_slice(v).map_err(|error| {
+    _from_slice(v, options).map_err(|error| {
         #[cfg(feature = "log")]
         log::warn!(
             "Unable to deserialize the slice {:?} into the type {}. Error: {error}",
@@ -814,6 +1087,101 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tracked_fields() {
+        use super::tracked::Tracked;
+
+        #[derive(Clone, Debug, PartialEq, Deserialize)]
+        struct ThreeFields {
+            a: u8,
+            b: Option<u8>,
+            c: u8,
+        }
+
+        let tracked: Tracked<ThreeFields> = crate::from_str("+CMD: 1,,3").unwrap();
+        assert_eq!(
+            tracked.value,
+            ThreeFields {
+                a: 1,
+                b: None,
+                c: 3
+            }
+        );
+        assert_eq!(tracked.present, 0b101);
+        assert!(!tracked.is_present(1));
+
+        let tracked: Tracked<ThreeFields> = crate::from_str("+CMD: 1,0,3").unwrap();
+        assert_eq!(tracked.b, Some(0));
+        assert!(tracked.is_present(1));
+
+        // Deserialized with the options of the caller
+        let options = super::DeserializeOptions {
+            equals_delimited_token: true,
+            ..Default::default()
+        };
+        let tracked: Tracked<ThreeFields> =
+            crate::from_slice_with_options(b"+CMD=1,,3", options).unwrap();
+        assert_eq!(tracked.c, 3);
+        assert_eq!(tracked.present, 0b101);
+    }
+
+    #[test]
+    fn multi_line() {
+        use super::multi_line::MultiLine;
+
+        let res: MultiLine<3, 32> =
+            crate::from_slice(b"Quectel\r\nBG96\r\nRevision: BG96MAR02A07M1G").unwrap();
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0], "Quectel");
+        assert_eq!(res[1], "BG96");
+        assert_eq!(res[2], "Revision: BG96MAR02A07M1G");
+
+        assert!(crate::from_slice::<MultiLine<2, 32>>(b"Quectel\r\nBG96\r\nRev").is_err());
+    }
+
+    #[test]
+    fn colon_list() {
+        use super::colon_list::ColonList;
+
+        #[derive(Clone, Debug, PartialEq, Deserialize)]
+        struct WithColonList {
+            a: u8,
+            list: ColonList<u8, 4>,
+            b: u8,
+        }
+
+        assert_eq!(
+            crate::from_str("+CMD: 1,2:3:4,5"),
+            Ok(WithColonList {
+                a: 1,
+                list: ColonList(heapless::Vec::from_slice(&[2, 3, 4]).unwrap()),
+                b: 5
+            })
+        );
+    }
+
+    #[test]
+    fn no_space_after_colon() {
+        #[derive(Clone, Debug, PartialEq, Deserialize)]
+        struct Indicator {
+            ind: u8,
+            value: u8,
+        }
+
+        assert_eq!(
+            crate::from_str("+CIEV:7,1"),
+            Ok(Indicator { ind: 7, value: 1 })
+        );
+    }
+
+    #[test]
+    fn tuple_struct() {
+        #[derive(Clone, Debug, PartialEq, Deserialize)]
+        struct Point(u8, u8);
+
+        assert_eq!(crate::from_str("+CMD: 1,2"), Ok(Point(1, 2)));
+    }
+
     #[test]
     fn simple_struct_optionals() {
         assert_eq!(
@@ -852,6 +1220,302 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn space_delimited_token() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct TwoFields {
+            a: u8,
+            b: u8,
+        }
+
+        let options = crate::DeserializeOptions {
+            space_delimited_token: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD 1,2", options),
+            Ok(TwoFields { a: 1, b: 2 })
+        );
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD: 1,2", options),
+            Ok(TwoFields { a: 1, b: 2 })
+        );
+        assert!(crate::from_slice::<TwoFields>(b"+CMD 1,2").is_err());
+    }
+
+    #[test]
+    fn variable_field_count() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct Registration {
+            n: u8,
+            stat: u8,
+            lac: Option<String<4>>,
+            ci: Option<String<4>>,
+            act: Option<u8>,
+            cause_type: Option<u8>,
+        }
+
+        assert_eq!(
+            crate::from_str("+CREG: 1,5"),
+            Ok(Registration {
+                n: 1,
+                stat: 5,
+                lac: None,
+                ci: None,
+                act: None,
+                cause_type: None,
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CREG: 5,5,\"A1B2\",\"C3D4\",7,0"),
+            Ok(Registration {
+                n: 5,
+                stat: 5,
+                lac: Some(String::try_from("A1B2").unwrap()),
+                ci: Some(String::try_from("C3D4").unwrap()),
+                act: Some(7),
+                cause_type: Some(0),
+            })
+        );
+    }
+
+    #[test]
+    fn quoted_first_field_with_commas() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct WithQuotedString {
+            s: String<16>,
+            n: u8,
+        }
+
+        assert_eq!(
+            crate::from_str("+CMD: \"a,b,c\",1"),
+            Ok(WithQuotedString {
+                s: String::try_from("a,b,c").unwrap(),
+                n: 1
+            })
+        );
+    }
+
+    #[test]
+    fn leading_at() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct TwoFields {
+            a: u8,
+            b: u8,
+        }
+
+        assert_eq!(crate::from_str("AT+CMD: 1,2"), Ok(TwoFields { a: 1, b: 2 }));
+        assert_eq!(crate::from_str("+CMD: 1,2"), Ok(TwoFields { a: 1, b: 2 }));
+    }
+
+    #[test]
+    fn comma_decimal_separator() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct Single {
+            v: f32,
+        }
+
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct FloatAndInt {
+            v: f64,
+            n: u8,
+        }
+
+        let options = crate::DeserializeOptions {
+            decimal_separator: Some(b','),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD: 1,5", options),
+            Ok(Single { v: 1.5 })
+        );
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD: -2,25,7", options),
+            Ok(FloatAndInt { v: -2.25, n: 7 })
+        );
+        assert_eq!(
+            crate::from_slice(b"+CMD: 1.5,7"),
+            Ok(FloatAndInt { v: 1.5, n: 7 })
+        );
+    }
+
+    #[test]
+    fn quoted_grouped_numbers() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct Single {
+            v: u32,
+        }
+
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct SignedAndUnsigned {
+            a: i32,
+            b: u16,
+        }
+
+        let options = crate::DeserializeOptions {
+            quoted_grouped_numbers: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD: \"1,234\"", options),
+            Ok(Single { v: 1234 })
+        );
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD: \"-1,234,567\",\"12\"", options),
+            Ok(SignedAndUnsigned {
+                a: -1_234_567,
+                b: 12
+            })
+        );
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD: -5,7", options),
+            Ok(SignedAndUnsigned { a: -5, b: 7 })
+        );
+        assert_eq!(
+            crate::from_slice_with_options::<Single>(b"+CMD: \"1,234", options),
+            Err(crate::de::Error::InvalidNumber)
+        );
+        assert!(crate::from_slice::<Single>(b"+CMD: \"1,234\"").is_err());
+    }
+
+    #[test]
+    fn equals_delimited_token() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct TwoFields {
+            a: u8,
+            b: u8,
+        }
+
+        let options = crate::DeserializeOptions {
+            equals_delimited_token: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD=1,2", options),
+            Ok(TwoFields { a: 1, b: 2 })
+        );
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD: 1,2", options),
+            Ok(TwoFields { a: 1, b: 2 })
+        );
+        assert!(crate::from_slice::<TwoFields>(b"+CMD=1,2").is_err());
+    }
+
+    #[test]
+    fn right_aligned_numbers() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct Aligned {
+            a: u8,
+            b: i16,
+        }
+
+        assert_eq!(
+            crate::from_slice(b"+CMD:   5 ,  6"),
+            Ok(Aligned { a: 5, b: 6 })
+        );
+        assert_eq!(
+            crate::from_slice(b"+CMD:  15,  -6  "),
+            Ok(Aligned { a: 15, b: -6 })
+        );
+        assert_eq!(
+            crate::from_slice(b"+CMD:5 ,6\r\n"),
+            Ok(Aligned { a: 5, b: 6 })
+        );
+    }
+
+    #[test]
+    fn newline_separated_fields() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct MultiLine {
+            a: u8,
+            s: String<16>,
+            q: String<16>,
+            b: u8,
+        }
+
+        let options = crate::DeserializeOptions {
+            newline_separated_fields: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD:\r\n1\r\nabc\r\n\"d,e\"\r\n2\r\n", options),
+            Ok(MultiLine {
+                a: 1,
+                s: String::try_from("abc").unwrap(),
+                q: String::try_from("d,e").unwrap(),
+                b: 2,
+            })
+        );
+        assert_eq!(
+            crate::from_slice_with_options(b"+CMD: 1,abc\n\"d,e\",2", options),
+            Ok(MultiLine {
+                a: 1,
+                s: String::try_from("abc").unwrap(),
+                q: String::try_from("d,e").unwrap(),
+                b: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn string_too_long() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct StringTest {
+            string: String<8>,
+        }
+
+        assert_eq!(
+            crate::from_str::<StringTest>("+CGMI: \"u-blox AG\""),
+            Err(crate::de::Error::InvalidLength {
+                len: 9,
+                expected: Some(8),
+                field: Some(0),
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CGMI: \"u-blox\""),
+            Ok(StringTest {
+                string: String::try_from("u-blox").unwrap()
+            })
+        );
+    }
+
+    #[test]
+    fn empty_quoted_string() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct EmptyFirst {
+            apn: String<8>,
+            cid: u8,
+        }
+
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct EmptyLast {
+            cid: u8,
+            apn: String<8>,
+        }
+
+        assert_eq!(
+            crate::from_str("+CMD: \"\",5"),
+            Ok(EmptyFirst {
+                apn: String::new(),
+                cid: 5
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CMD: 5,\"\""),
+            Ok(EmptyLast {
+                cid: 5,
+                apn: String::new(),
+            })
+        );
+    }
+
     #[test]
     fn simple_string() {
         #[derive(Clone, Debug, Deserialize, PartialEq)]
@@ -1249,6 +1913,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn qgpsloc_empty_fields() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct GpsLocation {
+            pub utc: Option<f64>,
+            pub latitude: Option<f64>,
+            pub longitude: Option<f64>,
+            pub hdop: Option<f32>,
+            pub altitude: Option<f32>,
+            pub fix: u8,
+            pub course_over_ground: Option<f32>,
+            pub speed_km: Option<f32>,
+            pub speed_knots: Option<f32>,
+            pub date: u32,
+            pub nsat: Option<u8>,
+        }
+
+        // Several empty fields in the middle
+        assert_eq!(
+            crate::from_str("+QGPSLOC: 061951.000,31.50722,117.11929,,62.2,2,,,,110513,09"),
+            Ok(GpsLocation {
+                utc: Some(61951.0),
+                latitude: Some(31.50722),
+                longitude: Some(117.11929),
+                hdop: None,
+                altitude: Some(62.2),
+                fix: 2,
+                course_over_ground: None,
+                speed_km: None,
+                speed_knots: None,
+                date: 110513,
+                nsat: Some(9),
+            })
+        );
+
+        // Leading and trailing empty fields
+        assert_eq!(
+            crate::from_str("+QGPSLOC: ,31.50722,117.11929,0.7,,2,,0.0,0.0,110513,"),
+            Ok(GpsLocation {
+                utc: None,
+                latitude: Some(31.50722),
+                longitude: Some(117.11929),
+                hdop: Some(0.7),
+                altitude: None,
+                fix: 2,
+                course_over_ground: None,
+                speed_km: Some(0.0),
+                speed_knots: Some(0.0),
+                date: 110513,
+                nsat: None,
+            })
+        );
+    }
+
+    #[test]
+    fn quoted_enum_variant() {
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub enum PinStatusCode {
+            #[serde(rename = "READY")]
+            Ready,
+            #[serde(rename = "SIM PIN")]
+            SimPin,
+        }
+
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct PinStatus {
+            pub code: PinStatusCode,
+        }
+
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct PinStatusWithRetries {
+            pub code: PinStatusCode,
+            pub retries: u8,
+        }
+
+        assert_eq!(
+            crate::from_str("+CPIN: \"READY\""),
+            Ok(PinStatus {
+                code: PinStatusCode::Ready
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CPIN: \"SIM PIN\""),
+            Ok(PinStatus {
+                code: PinStatusCode::SimPin
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CPIN: READY"),
+            Ok(PinStatus {
+                code: PinStatusCode::Ready
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CPIN: \"SIM PIN\",3"),
+            Ok(PinStatusWithRetries {
+                code: PinStatusCode::SimPin,
+                retries: 3
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CPIN: SIM PIN,3"),
+            Ok(PinStatusWithRetries {
+                code: PinStatusCode::SimPin,
+                retries: 3
+            })
+        );
+    }
+
+    #[test]
+    fn non_zero() {
+        use core::num::{NonZeroU16, NonZeroU8};
+
+        #[derive(Clone, Debug, Deserialize, PartialEq)]
+        pub struct SocketInfo {
+            pub socket_id: NonZeroU8,
+            pub port: NonZeroU16,
+        }
+
+        assert_eq!(
+            crate::from_str("+USOCR: 3,8080"),
+            Ok(SocketInfo {
+                socket_id: NonZeroU8::new(3).unwrap(),
+                port: NonZeroU16::new(8080).unwrap(),
+            })
+        );
+        assert_eq!(
+            crate::from_str::<SocketInfo>("+USOCR: 0,8080"),
+            Err(crate::de::Error::InvalidNumber)
+        );
+        assert_eq!(
+            crate::from_str::<SocketInfo>("+USOCR: 3,0"),
+            Err(crate::de::Error::InvalidNumber)
+        );
+    }
+
     #[test]
     fn gpsant() {
         #[derive(Clone, Debug, Deserialize, PartialEq)]
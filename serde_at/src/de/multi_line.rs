@@ -0,0 +1,73 @@
+//! Parsing of unlabeled multi-line responses.
+//!
+use core::fmt;
+use core::ops::Deref;
+
+use heapless::{String, Vec};
+use serde::{de, Deserialize, Deserializer};
+
+/// Structure for parsing a response consisting of unlabeled lines.
+///
+/// Each non-empty line is parsed into a `String<M>`, with surrounding
+/// whitespace trimmed. As all remaining input is consumed, this can only be
+/// used as the whole response, or as the last parameter.
+///
+/// For example:
+///
+/// For the response to `ATI`: `Quectel\r\nBG96\r\nRevision: BG96MAR02A07M1G`
+///
+/// We can parse the response as a `MultiLine<3, 32>` which yields:
+/// `MultiLine(["Quectel", "BG96", "Revision: BG96MAR02A07M1G"])`
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiLine<const N: usize, const M: usize>(pub Vec<String<M>, N>);
+
+impl<const N: usize, const M: usize> Deref for MultiLine<N, M> {
+    type Target = Vec<String<M>, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de, const N: usize, const M: usize> Deserialize<'de> for MultiLine<N, M> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // As with `LengthDelimited`, deserialize_tuple is used to get hold of
+        // the remaining input, as deserialize_bytes clips it at the first comma.
+        deserializer.deserialize_tuple(N, MultiLineVisitor::<N, M>)
+    }
+}
+
+struct MultiLineVisitor<const N: usize, const M: usize>;
+
+impl<'de, const N: usize, const M: usize> de::Visitor<'de> for MultiLineVisitor<N, M> {
+    type Value = MultiLine<N, M>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("lines separated by \"\\r\\n\"")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let mut lines = Vec::new();
+        for line in v.split(|&c| c == b'\n') {
+            let line = line.trim_ascii();
+            if line.is_empty() {
+                continue;
+            }
+            let line = core::str::from_utf8(line)
+                .map_err(|_| de::Error::custom("invalid unicode in line"))?;
+            let line =
+                String::try_from(line).map_err(|_| de::Error::invalid_length(line.len(), &self))?;
+            lines
+                .push(line)
+                .map_err(|_| de::Error::invalid_length(N + 1, &self))?;
+        }
+        Ok(MultiLine(lines))
+    }
+}
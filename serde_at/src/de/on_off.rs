@@ -0,0 +1,93 @@
+use core::fmt;
+use core::ops::Deref;
+use serde::de::Visitor;
+use serde::{de, Deserialize};
+
+/// `OnOff`
+/// A boolean feature toggle, written in AT commands as `1` or `0`. Responses
+/// are accepted as either `0`/`1`, `ON`/`OFF` or `true`/`false`, optionally
+/// quoted and in any case.
+/// Can be dereferenced to its value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct OnOff(pub bool);
+
+impl Deref for OnOff {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<bool> for OnOff {
+    fn from(value: bool) -> Self {
+        Self(value)
+    }
+}
+
+struct OnOffVisitor;
+
+impl<'de> Visitor<'de> for OnOffVisitor {
+    type Value = OnOff;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("one of 0, 1, ON, OFF, true or false")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let v = v.trim_ascii();
+        let v = v
+            .strip_prefix(b"\"")
+            .and_then(|v| v.strip_suffix(b"\""))
+            .unwrap_or(v);
+
+        if v == b"1" || v.eq_ignore_ascii_case(b"on") || v.eq_ignore_ascii_case(b"true") {
+            Ok(OnOff(true))
+        } else if v == b"0" || v.eq_ignore_ascii_case(b"off") || v.eq_ignore_ascii_case(b"false") {
+            Ok(OnOff(false))
+        } else {
+            Err(de::Error::custom("Invalid on/off value"))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OnOff {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(OnOffVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OnOff;
+
+    #[test]
+    pub fn test_parsing_on_off() {
+        for input in [
+            "+CMD: 1",
+            "+CMD: ON",
+            "+CMD: \"ON\"",
+            "+CMD: on",
+            "+CMD: true",
+        ] {
+            assert_eq!(crate::from_str::<OnOff>(input), Ok(OnOff(true)), "{input}");
+        }
+        for input in [
+            "+CMD: 0",
+            "+CMD: OFF",
+            "+CMD: \"OFF\"",
+            "+CMD: off",
+            "+CMD: false",
+        ] {
+            assert_eq!(crate::from_str::<OnOff>(input), Ok(OnOff(false)), "{input}");
+        }
+        assert!(crate::from_str::<OnOff>("+CMD: 2").is_err());
+        assert!(crate::from_str::<OnOff>("+CMD: ONN").is_err());
+    }
+}
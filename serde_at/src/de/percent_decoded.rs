@@ -0,0 +1,124 @@
+//! Parsing of percent-encoded strings.
+//!
+use core::fmt;
+use core::ops::Deref;
+
+use heapless::{String, Vec};
+use serde::{de, Deserialize, Deserializer};
+
+/// Structure for parsing a string in which special characters are
+/// percent-encoded as `%XX`, with `XX` being the hex value of the byte.
+///
+/// For example:
+///
+/// For the response: `+CMD: "Hello%20World"`
+///
+/// We can parse the parameter as a `PercentDecoded<16>` which yields:
+/// `PercentDecoded("Hello World")`
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PercentDecoded<const N: usize>(pub String<N>);
+
+impl<const N: usize> Deref for PercentDecoded<N> {
+    type Target = String<N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for PercentDecoded<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(PercentDecodedVisitor::<N>)
+    }
+}
+
+struct PercentDecodedVisitor<const N: usize>;
+
+fn hex_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+impl<'de, const N: usize> de::Visitor<'de> for PercentDecodedVisitor<N> {
+    type Value = PercentDecoded<N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a percent-encoded string, e.g.: \"Hello%20World\"")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let mut decoded = Vec::<u8, N>::new();
+        let mut bytes = v.iter();
+        while let Some(&c) = bytes.next() {
+            let c = if c == b'%' {
+                match (
+                    bytes.next().and_then(|&c| hex_nibble(c)),
+                    bytes.next().and_then(|&c| hex_nibble(c)),
+                ) {
+                    (Some(hi), Some(lo)) => (hi << 4) | lo,
+                    _ => return Err(de::Error::custom("Invalid percent-encoding")),
+                }
+            } else {
+                c
+            };
+            decoded
+                .push(c)
+                .map_err(|_| de::Error::invalid_length(v.len(), &self))?;
+        }
+
+        String::from_utf8(decoded)
+            .map(PercentDecoded)
+            .map_err(|_| de::Error::custom("Invalid UTF-8"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PercentDecoded;
+    use heapless::String;
+    use serde_derive::Deserialize;
+
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    struct WithText<const N: usize> {
+        text: PercentDecoded<N>,
+        n: u8,
+    }
+
+    #[test]
+    fn percent_decoded() {
+        assert_eq!(
+            crate::from_str("+CMD: \"Hello%20World\",1"),
+            Ok(WithText::<16> {
+                text: PercentDecoded(String::try_from("Hello World").unwrap()),
+                n: 1
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CMD: \"50%25%2c%22x%22\",2"),
+            Ok(WithText::<16> {
+                text: PercentDecoded(String::try_from("50%,\"x\"").unwrap()),
+                n: 2
+            })
+        );
+        assert!(crate::from_str::<WithText<16>>("+CMD: \"100%\",1").is_err());
+        assert!(crate::from_str::<WithText<4>>("+CMD: \"Hello\",1").is_err());
+    }
+}
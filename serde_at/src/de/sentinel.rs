@@ -0,0 +1,71 @@
+//! Integer parameters using a sentinel value for "unknown".
+//!
+use core::ops::Deref;
+
+use serde::{Deserialize, Deserializer};
+
+/// An integer parameter where the value `UNKNOWN` means the value is not
+/// known or not detectable, deserialized as `None`.
+///
+/// Eg. the `<rssi>` and `<ber>` parameters of `+CSQ` use `99`, making
+/// `+CSQ: 99,99` deserialize to `Sentinel::<99>(None)` for both.
+///
+/// Can be dereferenced to its value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sentinel<const UNKNOWN: u8>(pub Option<u8>);
+
+impl<const UNKNOWN: u8> Deref for Sentinel<UNKNOWN> {
+    type Target = Option<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de, const UNKNOWN: u8> Deserialize<'de> for Sentinel<UNKNOWN> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = u8::deserialize(deserializer)?;
+        Ok(Self((value != UNKNOWN).then_some(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sentinel;
+    use serde_derive::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct SignalQuality {
+        rssi: Sentinel<99>,
+        ber: Sentinel<99>,
+    }
+
+    #[test]
+    fn csq() {
+        assert_eq!(
+            crate::from_str("+CSQ: 99,99"),
+            Ok(SignalQuality {
+                rssi: Sentinel(None),
+                ber: Sentinel(None),
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CSQ: 17,99"),
+            Ok(SignalQuality {
+                rssi: Sentinel(Some(17)),
+                ber: Sentinel(None),
+            })
+        );
+        assert_eq!(
+            crate::from_str("+CSQ: 0,3"),
+            Ok(SignalQuality {
+                rssi: Sentinel(Some(0)),
+                ber: Sentinel(Some(3)),
+            })
+        );
+        assert!(crate::from_str::<SignalQuality>("+CSQ: 256,99").is_err());
+    }
+}
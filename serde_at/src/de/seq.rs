@@ -1,22 +1,25 @@
 use serde::de;
 
-use crate::de::{Deserializer, Error, Result};
+use crate::de::{is_at_prefix, Deserializer, Error, Result};
 
 #[allow(clippy::module_name_repetitions)]
 pub struct SeqAccess<'a, 'b> {
     first: bool,
     count: usize,
     len: Option<usize>,
+    track: bool,
     de: &'a mut Deserializer<'b>,
 }
 
 impl<'a, 'b> SeqAccess<'a, 'b> {
     pub(crate) fn new(de: &'a mut Deserializer<'b>) -> Self {
         let len = de.struct_size_hint();
+        let track = core::mem::take(&mut de.track_fields);
         SeqAccess {
             de,
             first: true,
             len,
+            track,
             count: 0,
         }
     }
@@ -29,15 +32,24 @@ impl<'a, 'de> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
     where
         T: de::DeserializeSeed<'de>,
     {
+        let line_separated =
+            !self.first && self.de.options.newline_separated_fields && self.de.parse_line_break();
+
         match self.de.parse_whitespace() {
-            Some(b',') => {
+            Some(b',') if !self.first => {
                 self.de.eat_char();
                 self.de.parse_whitespace();
             }
+            Some(b',') => {
+                // Leading empty parameter, left for the element to parse as
+                // `None`, to keep the following parameters in position
+                self.first = false;
+            }
+            Some(_) if line_separated => {}
             Some(c) => {
                 if self.first {
                     self.first = false;
-                } else if c != b'+' {
+                } else if !is_at_prefix(c) {
                     if let Some(len) = self.len {
                         if self.count == len - 1 {
                             self.de.set_is_trailing_parsing();
@@ -55,6 +67,7 @@ impl<'a, 'de> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
             }
         };
 
+        let start = self.de.index;
         match seed.deserialize(&mut *self.de) {
             // Misuse EofWhileParsingObject here to indicate finished object in vec cases.
             // See matching TODO in `de::mod`..
@@ -62,8 +75,20 @@ impl<'a, 'de> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
                 self.count += 1;
                 Ok(None)
             }
+            Err(Error::InvalidLength {
+                len,
+                expected,
+                field: None,
+            }) => Err(Error::InvalidLength {
+                len,
+                expected,
+                field: Some(self.count),
+            }),
             Err(e) => Err(e),
             Ok(v) => {
+                if self.track && self.de.index > start && self.count < 32 {
+                    self.de.present |= 1 << self.count;
+                }
                 self.count += 1;
                 Ok(Some(v))
             }
@@ -0,0 +1,115 @@
+//! Tracking of which positional parameters are present in a response.
+//!
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use serde::de::IntoDeserializer;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::de::{Error, Result};
+
+/// Name used to signal the deserializer to record the parameters present
+pub(crate) const TRACKED: &str = "$serde_at::Tracked";
+
+/// Structure for deserializing a response, while recording which positional
+/// parameters were present.
+///
+/// This allows distinguishing an omitted parameter from one that was given
+/// with a default-like value, e.g. for the response `+CMD: 1,,3` the second
+/// parameter is recorded as absent, while `+CMD: 1,0,3` records it as present.
+///
+/// Only the parameters of the outermost struct or tuple of `T` are tracked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tracked<T> {
+    /// The deserialized value
+    pub value: T,
+    /// Bitmask of the parameters present, with bit `n` set if parameter `n`
+    /// was non-empty. Only the first 32 parameters are tracked.
+    pub present: u32,
+}
+
+impl<T> Tracked<T> {
+    /// Whether the parameter at position `index` was present
+    pub fn is_present(&self, index: usize) -> bool {
+        index < 32 && self.present & (1 << index) != 0
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Tracked<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(TRACKED, TrackedVisitor::<T>(PhantomData))
+    }
+}
+
+/// Hands the deserialized value, followed by the bitmask of the parameters
+/// recorded as present while deserializing it, to [`TrackedVisitor`].
+pub(crate) struct TrackedAccess<'a, 'b> {
+    de: &'a mut super::Deserializer<'b>,
+    count: usize,
+}
+
+impl<'a, 'b> TrackedAccess<'a, 'b> {
+    pub(crate) fn new(de: &'a mut super::Deserializer<'b>) -> Self {
+        Self { de, count: 0 }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for TrackedAccess<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        self.count += 1;
+        match self.count {
+            1 => seed.deserialize(&mut *self.de).map(Some),
+            2 => seed
+                .deserialize(self.de.present.into_deserializer())
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+struct TrackedVisitor<T>(PhantomData<T>);
+
+impl<'de, T> de::Visitor<'de> for TrackedVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Tracked<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an AT response")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let value = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let present = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(Tracked { value, present })
+    }
+}
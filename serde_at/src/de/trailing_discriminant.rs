@@ -0,0 +1,228 @@
+//! Parsing of enums with the discriminant following the value.
+//!
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use serde::{de, Deserialize, Deserializer};
+
+use crate::de::{Error, Result};
+
+/// Name used to signal the deserializer that the next enum has a trailing
+/// discriminant
+pub(crate) const TRAILING_DISCRIMINANT: &str = "$serde_at::TrailingDiscriminant";
+
+/// Structure for parsing an enum where the value precedes the discriminant.
+///
+/// Only unit and newtype variants are supported, and the value must be a
+/// single parameter. Unit variants are given by their discriminant alone,
+/// which takes precedence over reading it as the value of a newtype variant.
+///
+/// For example:
+///
+/// For the response: `+CMD: 5,1`
+///
+/// We can parse the parameters as a `TrailingDiscriminant<E>` which yields the
+/// variant of `E` with discriminant `1`, holding the value `5`. If `5` is the
+/// discriminant of a unit variant of `E` instead, that variant is yielded, and
+/// `1` is left for the following parameter.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrailingDiscriminant<T>(pub T);
+
+impl<T> Deref for TrailingDiscriminant<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for TrailingDiscriminant<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(
+            TRAILING_DISCRIMINANT,
+            TrailingDiscriminantVisitor::<T>(PhantomData),
+        )
+    }
+}
+
+struct TrailingDiscriminantVisitor<T>(PhantomData<T>);
+
+impl<'de, T> de::Visitor<'de> for TrailingDiscriminantVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = TrailingDiscriminant<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an enum with trailing discriminant, e.g.: \"5,1\"")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        if let Ok(Some(value)) = seq.next_element() {
+            return Ok(TrailingDiscriminant(value));
+        }
+        seq.next_element()?
+            .map(TrailingDiscriminant)
+            .ok_or_else(|| de::Error::invalid_length(0, &self))
+    }
+}
+
+/// Layout of the parameters of an enum with a trailing discriminant
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Layout {
+    /// The discriminant alone, as given for unit variants
+    Unit,
+    /// The value followed by the discriminant
+    Trailing,
+}
+
+/// Hands the enum read as a unit variant, followed by the enum read with its
+/// discriminant trailing the value, to [`TrailingDiscriminantVisitor`],
+/// rewinding the input ahead of each attempt.
+pub(crate) struct TrailingAccess<'a, 'b> {
+    de: &'a mut super::Deserializer<'b>,
+    start: usize,
+    count: usize,
+}
+
+impl<'a, 'b> TrailingAccess<'a, 'b> {
+    pub(crate) fn new(de: &'a mut super::Deserializer<'b>) -> Self {
+        let start = de.index;
+        Self {
+            de,
+            start,
+            count: 0,
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for TrailingAccess<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        self.count += 1;
+        let layout = match self.count {
+            1 => Layout::Unit,
+            2 => Layout::Trailing,
+            _ => return Ok(None),
+        };
+        self.de.index = self.start;
+        self.de.trailing_discriminant = Some(layout);
+        let result = seed.deserialize(&mut *self.de).map(Some);
+        self.de.trailing_discriminant = None;
+        result
+    }
+}
+
+pub(crate) struct TrailingVariantAccess<'a, 'b> {
+    de: &'a mut super::Deserializer<'b>,
+    value_start: usize,
+    has_value: bool,
+    end: usize,
+}
+
+impl<'a, 'b> TrailingVariantAccess<'a, 'b> {
+    pub(crate) fn new(de: &'a mut super::Deserializer<'b>, layout: Layout) -> Self {
+        let value_start = de.index;
+        if layout == Layout::Unit {
+            return TrailingVariantAccess {
+                de,
+                value_start,
+                has_value: false,
+                end: value_start,
+            };
+        }
+
+        let mut in_quotes = false;
+        let separator = de.slice[value_start..].iter().position(|&c| match c {
+            b'"' => {
+                in_quotes = !in_quotes;
+                false
+            }
+            b',' => !in_quotes,
+            _ => false,
+        });
+
+        // Without a separator, there is only the discriminant
+        let has_value = if let Some(separator) = separator {
+            de.index = value_start + separator + 1;
+            true
+        } else {
+            false
+        };
+
+        TrailingVariantAccess {
+            de,
+            value_start,
+            has_value,
+            end: value_start,
+        }
+    }
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for TrailingVariantAccess<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(mut self, seed: V) -> Result<(V::Value, Self)>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.de.parse_whitespace();
+        let variant = seed.deserialize(&mut *self.de)?;
+        self.end = self.de.index;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for TrailingVariantAccess<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        if self.has_value {
+            return Err(Error::CustomError);
+        }
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if !self.has_value {
+            return Err(Error::EofWhileParsingValue);
+        }
+        self.de.index = self.value_start;
+        let value = seed.deserialize(&mut *self.de)?;
+        self.de.index = self.end;
+        Ok(value)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Error::CustomError)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], _visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Error::CustomError)
+    }
+}
@@ -19,12 +19,15 @@ pub mod ser;
 pub use serde;
 
 #[doc(inline)]
-pub use self::de::{from_slice, from_str, hex_str::HexStr};
+pub use self::de::{
+    bcd::Bcd, from_slice, from_slice_with_options, from_str, hex_str::HexStr, mac_addr::MacAddr,
+    on_off::OnOff, DeserializeOptions,
+};
 #[doc(inline)]
-pub use self::ser::{to_slice, SerializeOptions};
+pub use self::ser::{to_params_slice, to_slice, to_slice_chained, SerializeCmd, SerializeOptions};
 
 #[cfg(feature = "heapless")]
-pub use self::ser::{to_string, to_vec};
+pub use self::ser::{to_existing_vec, to_string, to_vec, RawBytes};
 
 use core::mem::MaybeUninit;
 
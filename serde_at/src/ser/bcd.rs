@@ -0,0 +1,20 @@
+use crate::de::bcd::BCD_MAX_BYTES;
+use crate::Bcd;
+use serde::ser::Serialize;
+use serde::Serializer;
+
+impl Serialize for Bcd {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut bytes = [0; BCD_MAX_BYTES];
+        let mut nibbles = [0; BCD_MAX_BYTES * 2];
+        let bytes = self.to_bytes(&mut bytes);
+        for (i, b) in bytes.iter().enumerate() {
+            nibbles[i * 2] = b'0' + (b >> 4);
+            nibbles[i * 2 + 1] = b'0' + (b & 0x0F);
+        }
+        serializer.serialize_bytes(&nibbles[..bytes.len() * 2])
+    }
+}
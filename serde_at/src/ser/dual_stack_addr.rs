@@ -0,0 +1,43 @@
+use core::fmt::{Display, Write};
+
+use serde::ser::{Error as _, Serialize, SerializeTuple};
+use serde::Serializer;
+
+use super::FmtWrapper;
+use crate::de::dual_stack_addr::DualStackAddr;
+
+/// Longest textual IP address, eg. `ffff:ffff:ffff:ffff:ffff:ffff:255.255.255.255`
+const ADDR_MAX_LEN: usize = 45;
+
+/// An address serialized as a quoted string, empty if absent
+struct Addr<T>(Option<T>);
+
+impl<T: Display> Serialize for Addr<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf = [0; ADDR_MAX_LEN];
+        let mut wrapper = FmtWrapper::new(&mut buf);
+        if let Some(addr) = &self.0 {
+            write!(wrapper, "{addr}").map_err(S::Error::custom)?;
+        }
+        let len = wrapper.offset;
+        // Only the ASCII text of an address was written
+        serializer.serialize_str(core::str::from_utf8(&buf[..len]).unwrap())
+    }
+}
+
+impl Serialize for DualStackAddr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(1 + usize::from(self.ipv6.is_some()))?;
+        tuple.serialize_element(&Addr(self.ipv4))?;
+        if let Some(ipv6) = self.ipv6 {
+            tuple.serialize_element(&Addr(Some(ipv6)))?;
+        }
+        tuple.end()
+    }
+}
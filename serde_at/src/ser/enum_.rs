@@ -1,6 +1,39 @@
 use crate::ser::{Error, Result, Serializer};
 use serde::ser;
 
+pub struct SerializeTuple<'a, 'b> {
+    ser: &'a mut Serializer<'b>,
+    first: bool,
+}
+
+impl<'a, 'b> SerializeTuple<'a, 'b> {
+    pub(crate) fn new(ser: &'a mut Serializer<'b>) -> Self {
+        SerializeTuple { ser, first: true }
+    }
+}
+
+impl<'a, 'b> ser::SerializeTuple for SerializeTuple<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ser::Serialize + ?Sized,
+    {
+        if !self.first {
+            self.ser.push(b',')?;
+        }
+        self.first = false;
+
+        value.serialize(&mut *self.ser)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(())
+    }
+}
+
 pub struct SerializeTupleVariant<'a, 'b> {
     ser: &'a mut Serializer<'b>,
     first: bool,
@@ -1,8 +1,23 @@
+use super::HEX_STR_DEFAULT_CASE;
 use crate::HexStr;
 use core::fmt::Write;
 use serde::ser::Serialize;
 use serde::Serializer;
 
+/// Serialize the formatted hex string, signalling the serializer if the case
+/// was left at its default, so that it can apply
+/// [`SerializeOptions::hex_caps`](crate::SerializeOptions::hex_caps).
+fn serialize_hex<S, T>(serializer: S, hex: &str, hex_str: &HexStr<T>) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if hex_str.hex_in_caps && !hex_str.fixed_case {
+        serializer.serialize_newtype_struct(HEX_STR_DEFAULT_CASE, hex)
+    } else {
+        serializer.serialize_str(hex)
+    }
+}
+
 macro_rules! impl_hex_str_serialize {
     ($type:ty, $len:expr, $len_delimited:expr, $hex_size:literal) => {
         impl Serialize for HexStr<$type> {
@@ -47,7 +62,7 @@ macro_rules! impl_hex_str_serialize {
                         string.push(c).unwrap();
                     }
 
-                    serializer.serialize_str(string.as_str())
+                    serialize_hex(serializer, string.as_str(), self)
                 } else {
                     let mut string = heapless::String::<$len>::new();
                     if self.skip_last_0_values {
@@ -78,7 +93,7 @@ macro_rules! impl_hex_str_serialize {
                             string.push(c).unwrap();
                         }
                     }
-                    serializer.serialize_str(string.as_str())
+                    serialize_hex(serializer, string.as_str(), self)
                 }
             }
         }
@@ -90,10 +105,16 @@ impl_hex_str_serialize!(u16, 12, 18, 4);
 impl_hex_str_serialize!(u32, 20, 30, 8);
 impl_hex_str_serialize!(u64, 36, 66, 16);
 impl_hex_str_serialize!(u128, 68, 130, 32);
+impl_hex_str_serialize!(i8, 8, 10, 2);
+impl_hex_str_serialize!(i16, 12, 18, 4);
+impl_hex_str_serialize!(i32, 20, 30, 8);
+impl_hex_str_serialize!(i64, 36, 66, 16);
+impl_hex_str_serialize!(i128, 68, 130, 32);
 
 #[cfg(feature = "hex_str_arrays")]
 mod unstable {
 
+    use super::serialize_hex;
     use crate::HexStr;
     use core::fmt::Write;
     use serde::{Serialize, Serializer};
@@ -147,7 +168,7 @@ mod unstable {
                     }
                 }
             }
-            serializer.serialize_str(string.as_str())
+            serialize_hex(serializer, string.as_str(), self)
         }
     }
 }
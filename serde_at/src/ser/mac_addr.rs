@@ -0,0 +1,20 @@
+use crate::MacAddr;
+use serde::ser::Serialize;
+use serde::Serializer;
+
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+impl Serialize for MacAddr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf = [b':'; 17];
+        for (i, octet) in self.0.iter().enumerate() {
+            buf[i * 3] = HEX[usize::from(octet >> 4)];
+            buf[i * 3 + 1] = HEX[usize::from(octet & 0x0F)];
+        }
+        // Only ASCII hex digits and colons were written
+        serializer.serialize_str(core::str::from_utf8(&buf).unwrap())
+    }
+}
@@ -4,21 +4,36 @@ use core::fmt;
 
 use serde::ser;
 
+mod bcd;
+mod dual_stack_addr;
 mod enum_;
 #[cfg(feature = "heapless")]
 mod hex_str;
+mod mac_addr;
+mod on_off;
+#[cfg(feature = "heapless")]
+mod raw_bytes;
 mod struct_;
 
-use self::enum_::{SerializeStructVariant, SerializeTupleVariant};
+use self::enum_::{SerializeStructVariant, SerializeTuple, SerializeTupleVariant};
 use self::struct_::SerializeStruct;
 
+#[cfg(feature = "heapless")]
+pub use self::raw_bytes::RawBytes;
+
 /// Serialization result
 pub type Result<T> = ::core::result::Result<T, Error>;
 
 /// Options used by the serializer, to customize the resulting string
+///
+/// Constructed through [`SerializeOptions::default`] and the builder methods,
+/// eg. `SerializeOptions::default().value_sep(false)`, so that options can be
+/// added without breaking existing code.
+#[non_exhaustive]
 pub struct SerializeOptions<'a> {
     /// Whether or not to include `=` as a seperator between the at command, and
-    /// the parameters (serialized struct fields)
+    /// the parameters (serialized struct fields). The separator is never
+    /// added after an empty command.
     ///
     /// **default**: true
     pub value_sep: bool,
@@ -34,6 +49,28 @@ pub struct SerializeOptions<'a> {
     ///
     /// **default**: true
     pub escape_strings: bool,
+    /// Whether to append an NMEA style checksum `*HH` before the termination,
+    /// computed as the XOR of all bytes following the prefix.
+    ///
+    /// **default**: false
+    pub nmea_checksum: bool,
+    /// Case to serialize [`HexStr`](crate::HexStr) values in, when not set
+    /// per value.
+    ///
+    /// Values with `hex_in_caps` left at its default of `true` follow this
+    /// option when set, unless `fixed_case` is set on the value. Values
+    /// explicitly setting `hex_in_caps: false` always stay in small case.
+    ///
+    /// **default**: None
+    pub hex_caps: Option<bool>,
+    /// Whether to emit the fields of the command in reverse positional order,
+    /// eg. for modems expecting the arguments the other way around.
+    ///
+    /// Fields serializing to nothing, such as `None`, are kept as empty
+    /// parameters rather than omitted.
+    ///
+    /// **default**: false
+    pub reverse_fields: bool,
 }
 
 impl<'a> Default for SerializeOptions<'a> {
@@ -43,10 +80,68 @@ impl<'a> Default for SerializeOptions<'a> {
             cmd_prefix: "AT",
             termination: "\r",
             escape_strings: true,
+            nmea_checksum: false,
+            hex_caps: None,
+            reverse_fields: false,
         }
     }
 }
 
+impl<'a> SerializeOptions<'a> {
+    /// Set [`SerializeOptions::value_sep`]
+    #[must_use]
+    pub const fn value_sep(mut self, value_sep: bool) -> Self {
+        self.value_sep = value_sep;
+        self
+    }
+
+    /// Set [`SerializeOptions::cmd_prefix`]
+    #[must_use]
+    pub const fn cmd_prefix(mut self, cmd_prefix: &'a str) -> Self {
+        self.cmd_prefix = cmd_prefix;
+        self
+    }
+
+    /// Set [`SerializeOptions::termination`]
+    #[must_use]
+    pub const fn termination(mut self, termination: &'a str) -> Self {
+        self.termination = termination;
+        self
+    }
+
+    /// Set [`SerializeOptions::escape_strings`]
+    #[must_use]
+    pub const fn escape_strings(mut self, escape_strings: bool) -> Self {
+        self.escape_strings = escape_strings;
+        self
+    }
+
+    /// Set [`SerializeOptions::nmea_checksum`]
+    #[must_use]
+    pub const fn nmea_checksum(mut self, nmea_checksum: bool) -> Self {
+        self.nmea_checksum = nmea_checksum;
+        self
+    }
+
+    /// Set [`SerializeOptions::hex_caps`]
+    #[must_use]
+    pub const fn hex_caps(mut self, hex_caps: Option<bool>) -> Self {
+        self.hex_caps = hex_caps;
+        self
+    }
+
+    /// Set [`SerializeOptions::reverse_fields`]
+    #[must_use]
+    pub const fn reverse_fields(mut self, reverse_fields: bool) -> Self {
+        self.reverse_fields = reverse_fields;
+        self
+    }
+}
+
+/// Name used by `HexStr` to signal the serializer that the case of the value
+/// was left at its default
+pub(crate) const HEX_STR_DEFAULT_CASE: &str = "$serde_at::HexStrDefaultCase";
+
 /// This type represents all possible errors that can occur when serializing AT
 /// Command strings
 #[derive(Debug)]
@@ -101,6 +196,27 @@ impl<'a> Serializer<'a> {
         }
     }
 
+    /// Append the termination, preceded by the checksum if enabled
+    fn terminate(&mut self) -> Result<()> {
+        if self.options.nmea_checksum {
+            let start = self.options.cmd_prefix.len().min(self.written);
+            let checksum = self.buf[start..self.written]
+                .iter()
+                .fold(0, |acc, b| acc ^ b);
+            let hex = |nibble: u8| {
+                if nibble < 10 {
+                    b'0' + nibble
+                } else {
+                    b'A' + nibble - 10
+                }
+            };
+            self.push(b'*')?;
+            self.push(hex(checksum >> 4))?;
+            self.push(hex(checksum & 0x0F))?;
+        }
+        self.extend_from_slice(self.options.termination.as_bytes())
+    }
+
     fn write_buf(&mut self) -> &mut [u8] {
         &mut self.buf[self.written..]
     }
@@ -226,7 +342,7 @@ impl<'a, 'b> ser::Serializer for &'a mut Serializer<'b> {
     type Ok = ();
     type Error = Error;
     type SerializeSeq = Unreachable;
-    type SerializeTuple = Unreachable;
+    type SerializeTuple = SerializeTuple<'a, 'b>;
     type SerializeTupleStruct = Unreachable;
     type SerializeTupleVariant = SerializeTupleVariant<'a, 'b>;
     type SerializeMap = Unreachable;
@@ -344,6 +460,7 @@ impl<'a, 'b> ser::Serializer for &'a mut Serializer<'b> {
     fn serialize_none(self) -> Result<Self::Ok> {
         if self.written == self.options.cmd_prefix.len() + self.cmd.len() + 1
             && self.options.value_sep
+            && !self.cmd.is_empty()
         {
             self.written -= 1;
         }
@@ -364,8 +481,7 @@ impl<'a, 'b> ser::Serializer for &'a mut Serializer<'b> {
     fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {
         self.extend_from_slice(self.options.cmd_prefix.as_bytes())?;
         self.extend_from_slice(self.cmd.as_bytes())?;
-        self.extend_from_slice(self.options.termination.as_bytes())?;
-        Ok(())
+        self.terminate()
     }
 
     fn serialize_unit_variant(
@@ -377,10 +493,16 @@ impl<'a, 'b> ser::Serializer for &'a mut Serializer<'b> {
         self.serialize_u32(variant_index)
     }
 
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok>
     where
         T: ser::Serialize + ?Sized,
     {
+        if name == HEX_STR_DEFAULT_CASE && self.options.hex_caps == Some(false) {
+            let start = self.written;
+            value.serialize(&mut *self)?;
+            self.buf[start..self.written].make_ascii_lowercase();
+            return Ok(());
+        }
         value.serialize(self)
     }
 
@@ -404,7 +526,7 @@ impl<'a, 'b> ser::Serializer for &'a mut Serializer<'b> {
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
-        unreachable!()
+        Ok(SerializeTuple::new(self))
     }
 
     fn serialize_tuple_struct(
@@ -492,6 +614,32 @@ where
     Ok(buf)
 }
 
+#[cfg(feature = "heapless")]
+/// Serializes the given data structure into an existing byte vector,
+/// replacing its previous contents
+pub fn to_existing_vec<T, const N: usize>(
+    value: &T,
+    cmd: &str,
+    buf: &mut heapless::Vec<u8, N>,
+    options: SerializeOptions<'_>,
+) -> Result<()>
+where
+    T: ser::Serialize + ?Sized,
+{
+    buf.clear();
+    buf.resize_default(N).map_err(|_| Error::BufferFull)?;
+    match to_slice(value, cmd, buf, options) {
+        Ok(len) => {
+            buf.truncate(len);
+            Ok(())
+        }
+        Err(e) => {
+            buf.clear();
+            Err(e)
+        }
+    }
+}
+
 /// Serializes the given data structure to a buffer
 pub fn to_slice<T>(
     value: &T,
@@ -507,6 +655,81 @@ where
     Ok(ser.written)
 }
 
+/// Serializes only the parameters of the given data structure to a buffer,
+/// omitting the command prefix, the command itself and the termination.
+///
+/// Useful for building compound commands, or for logging just the arguments.
+pub fn to_params_slice<T>(value: &T, buf: &mut [u8], options: SerializeOptions<'_>) -> Result<usize>
+where
+    T: ser::Serialize + ?Sized,
+{
+    let options = SerializeOptions {
+        value_sep: false,
+        cmd_prefix: "",
+        termination: "",
+        nmea_checksum: false,
+        ..options
+    };
+    to_slice(value, "", buf, options)
+}
+
+/// Object safe serialization of a command, allowing commands of different
+/// types to be chained through [`to_slice_chained`].
+///
+/// Implemented for all types implementing `Serialize`.
+pub trait SerializeCmd {
+    /// Serializes `self` as the command `cmd`, like [`to_slice`]
+    fn serialize_cmd(
+        &self,
+        cmd: &str,
+        buf: &mut [u8],
+        options: SerializeOptions<'_>,
+    ) -> Result<usize>;
+}
+
+impl<T> SerializeCmd for T
+where
+    T: ser::Serialize,
+{
+    fn serialize_cmd(
+        &self,
+        cmd: &str,
+        buf: &mut [u8],
+        options: SerializeOptions<'_>,
+    ) -> Result<usize> {
+        to_slice(self, cmd, buf, options)
+    }
+}
+
+/// Serializes several commands into a single command line, separated by `;`,
+/// eg. `AT+A=1;+B;+C="x"\r`.
+///
+/// The prefix is only written ahead of the first command, and the termination
+/// only after the last, as given by `options`. NMEA checksums are not
+/// supported for chained commands, and `options.nmea_checksum` is ignored.
+pub fn to_slice_chained(
+    cmds: &[(&str, &dyn SerializeCmd)],
+    buf: &mut [u8],
+    options: SerializeOptions<'_>,
+) -> Result<usize> {
+    let mut written = 0;
+    for (i, (cmd, value)) in cmds.iter().enumerate() {
+        let options = SerializeOptions {
+            cmd_prefix: if i == 0 { options.cmd_prefix } else { ";" },
+            termination: "",
+            nmea_checksum: false,
+            ..options
+        };
+        written += value.serialize_cmd(cmd, &mut buf[written..], options)?;
+    }
+
+    let termination = options.termination.as_bytes();
+    buf.get_mut(written..written + termination.len())
+        .ok_or(Error::BufferFull)?
+        .copy_from_slice(termination);
+    Ok(written + termination.len())
+}
+
 impl ser::Error for Error {
     fn custom<T>(_msg: T) -> Self {
         unreachable!()
@@ -567,19 +790,6 @@ impl ser::SerializeSeq for Unreachable {
     }
 }
 
-impl ser::SerializeTuple for Unreachable {
-    type Ok = ();
-    type Error = Error;
-
-    fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<()> {
-        unreachable!()
-    }
-
-    fn end(self) -> Result<Self::Ok> {
-        unreachable!()
-    }
-}
-
 #[cfg(all(test, feature = "heapless"))]
 mod tests {
     use super::*;
@@ -647,6 +857,61 @@ mod tests {
         assert_eq!(s, String::<32>::try_from("4,15").unwrap());
     }
 
+    #[test]
+    fn existing_vec_reuse() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct Value {
+            a: u8,
+        }
+
+        let mut buf = heapless::Vec::<u8, 32>::new();
+
+        to_existing_vec(
+            &Value { a: 15 },
+            "+CMD",
+            &mut buf,
+            SerializeOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(&buf[..], b"AT+CMD=15\r");
+
+        to_existing_vec(
+            &Value { a: 3 },
+            "+CMD",
+            &mut buf,
+            SerializeOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(&buf[..], b"AT+CMD=3\r");
+
+        let mut small = heapless::Vec::<u8, 4>::new();
+        assert!(matches!(
+            to_existing_vec(
+                &Value { a: 15 },
+                "+CMD",
+                &mut small,
+                SerializeOptions::default()
+            ),
+            Err(Error::BufferFull)
+        ));
+        assert!(small.is_empty());
+    }
+
+    #[test]
+    fn params_only() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct Params {
+            a: u8,
+            b: u8,
+            c: u8,
+        }
+
+        let params = Params { a: 1, b: 2, c: 3 };
+        let mut buf = [0; 32];
+        let len = to_params_slice(&params, &mut buf, SerializeOptions::default()).unwrap();
+        assert_eq!(&buf[..len], b"1,2,3");
+    }
+
     #[test]
     fn newtype_struct() {
         let s: String<32> = to_string(&Handle(15), "", SerializeOptions::default()).unwrap();
@@ -776,6 +1041,20 @@ mod tests {
         assert_eq!(s, String::<32>::try_from("AT+CMD=Some bytes\r").unwrap());
     }
 
+    #[test]
+    fn raw_byte_vec_serialize() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct WithByteVec {
+            data: RawBytes<16>,
+        }
+        let b = WithByteVec {
+            data: RawBytes(heapless::Vec::from_slice(b"\x01\x02raw").unwrap()),
+        };
+        let mut buf = [0; 32];
+        let len = to_slice(&b, "+CMD", &mut buf, SerializeOptions::default()).unwrap();
+        assert_eq!(&buf[..len], b"AT+CMD=\x01\x02raw\r");
+    }
+
     #[test]
     fn nested_struct() {
         #[derive(Clone, PartialEq, Serialize)]
@@ -837,6 +1116,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_no_0x_caps: HexStr {
                 val: 0x55AA,
@@ -845,6 +1125,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_0x_small_case: HexStr {
                 val: 0x00FF,
@@ -853,6 +1134,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_no_0x_small_case: HexStr {
                 val: 0xAA55,
@@ -861,6 +1143,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_0x_caps_delimiter: HexStr {
                 val: 0xFF00,
@@ -869,6 +1152,7 @@ mod tests {
                 delimiter: ':',
                 delimiter_after_nibble_count: 1,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_no_0x_caps_delimiter: HexStr {
                 val: 0x55AA,
@@ -877,6 +1161,7 @@ mod tests {
                 delimiter: '-',
                 delimiter_after_nibble_count: 2,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_0x_small_case_delimiter: HexStr {
                 val: 0x00FF,
@@ -885,6 +1170,7 @@ mod tests {
                 delimiter: ':',
                 delimiter_after_nibble_count: 1,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_no_0x_small_case_delimiter: HexStr {
                 val: 0xAA5500FF,
@@ -893,6 +1179,7 @@ mod tests {
                 delimiter: '-',
                 delimiter_after_nibble_count: 2,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
         };
         let options = SerializeOptions {
@@ -915,6 +1202,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
             val_no_0x_caps: HexStr {
                 val: 0x55AA,
@@ -923,6 +1211,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
             val_0x_small_case: HexStr {
                 val: 0x00FF,
@@ -931,6 +1220,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
             val_no_0x_small_case: HexStr {
                 val: 0xAA55,
@@ -939,6 +1229,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
             val_0x_caps_delimiter: HexStr {
                 val: 0xFF00,
@@ -947,6 +1238,7 @@ mod tests {
                 delimiter: ':',
                 delimiter_after_nibble_count: 1,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
             val_no_0x_caps_delimiter: HexStr {
                 val: 0x55AA,
@@ -955,6 +1247,7 @@ mod tests {
                 delimiter: '-',
                 delimiter_after_nibble_count: 2,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
             val_0x_small_case_delimiter: HexStr {
                 val: 0x00FF,
@@ -963,6 +1256,7 @@ mod tests {
                 delimiter: ':',
                 delimiter_after_nibble_count: 1,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
             val_no_0x_small_case_delimiter: HexStr {
                 val: 0xAA5500FF,
@@ -971,6 +1265,7 @@ mod tests {
                 delimiter: '-',
                 delimiter_after_nibble_count: 2,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
         };
         let options = SerializeOptions {
@@ -987,6 +1282,290 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nmea_checksum() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct SetFixInterval {
+            interval: u16,
+        }
+
+        let options = SerializeOptions {
+            value_sep: false,
+            cmd_prefix: "$",
+            termination: "\r\n",
+            nmea_checksum: true,
+            ..Default::default()
+        };
+        let s: String<32> =
+            to_string(&SetFixInterval { interval: 1000 }, "PMTK220,", options).unwrap();
+        assert_eq!(s, String::<32>::try_from("$PMTK220,1000*1F\r\n").unwrap());
+    }
+
+    #[test]
+    fn bcd_serialize() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct WithBcd {
+            even: crate::Bcd,
+            odd: crate::Bcd,
+        }
+
+        let params = WithBcd {
+            even: crate::Bcd(1234),
+            odd: crate::Bcd(123),
+        };
+        let s: String<32> = to_string(&params, "+CMD", SerializeOptions::default()).unwrap();
+        assert_eq!(s, String::<32>::try_from("AT+CMD=1234,0123\r").unwrap());
+    }
+
+    #[test]
+    fn on_off_serialize() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct WithOnOff {
+            a: crate::OnOff,
+            b: crate::OnOff,
+        }
+
+        let params = WithOnOff {
+            a: crate::OnOff(true),
+            b: crate::OnOff(false),
+        };
+        let s: String<32> = to_string(&params, "+CMD", SerializeOptions::default()).unwrap();
+        assert_eq!(s, String::<32>::try_from("AT+CMD=1,0\r").unwrap());
+    }
+
+    #[test]
+    fn chained_commands() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct WithValue {
+            a: u8,
+        }
+
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct NoParams;
+
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct WithString<'a> {
+            s: &'a str,
+        }
+
+        let mut buf = [0; 64];
+        let len = to_slice_chained(
+            &[
+                ("+A", &WithValue { a: 1 }),
+                ("+B", &NoParams),
+                ("+C", &WithString { s: "x" }),
+            ],
+            &mut buf,
+            SerializeOptions {
+                termination: "\r\n",
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(&buf[..len], b"AT+A=1;+B;+C=\"x\"\r\n");
+
+        let mut buf = [0; 12];
+        assert!(to_slice_chained(
+            &[
+                ("+A", &WithValue { a: 1 }),
+                ("+B", &NoParams),
+                ("+C", &NoParams)
+            ],
+            &mut buf,
+            SerializeOptions::default(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn mac_addr_round_trip() {
+        #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+        pub struct WithMacAddr {
+            mac: crate::MacAddr,
+        }
+
+        let params = WithMacAddr {
+            mac: crate::MacAddr([0x01, 0x23, 0x45, 0x67, 0x89, 0xab]),
+        };
+        let s: String<40> =
+            to_string(&params, "+UWAPMACADDR", SerializeOptions::default()).unwrap();
+        assert_eq!(
+            s,
+            String::<40>::try_from("AT+UWAPMACADDR=\"01:23:45:67:89:AB\"\r").unwrap()
+        );
+
+        let parsed: WithMacAddr = crate::from_str("+UWAPMACADDR: \"01:23:45:67:89:ab\"").unwrap();
+        assert_eq!(parsed, params);
+    }
+
+    #[test]
+    fn dual_stack_addr_round_trip() {
+        use crate::de::dual_stack_addr::DualStackAddr;
+        use core::net::{Ipv4Addr, Ipv6Addr};
+
+        #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+        pub struct PdpAddress {
+            cid: u8,
+            addr: DualStackAddr,
+        }
+
+        let ipv6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        for (addr, params_str) in [
+            (
+                DualStackAddr {
+                    ipv4: Some(Ipv4Addr::new(10, 0, 0, 2)),
+                    ipv6: Some(ipv6),
+                },
+                "1,\"10.0.0.2\",\"2001:db8::1\"",
+            ),
+            (
+                DualStackAddr {
+                    ipv4: Some(Ipv4Addr::new(10, 0, 0, 2)),
+                    ipv6: None,
+                },
+                "1,\"10.0.0.2\"",
+            ),
+            (
+                DualStackAddr {
+                    ipv4: None,
+                    ipv6: Some(ipv6),
+                },
+                "1,\"\",\"2001:db8::1\"",
+            ),
+        ] {
+            let params = PdpAddress { cid: 1, addr };
+            let s: String<64> =
+                to_string(&params, "+CGPADDR", SerializeOptions::default()).unwrap();
+            let mut expected = String::<64>::try_from("AT+CGPADDR=").unwrap();
+            expected.push_str(params_str).unwrap();
+            expected.push('\r').unwrap();
+            assert_eq!(s, expected);
+
+            let mut response = String::<64>::try_from("+CGPADDR: ").unwrap();
+            response.push_str(params_str).unwrap();
+            let parsed: PdpAddress = crate::from_str(&response).unwrap();
+            assert_eq!(parsed, params);
+        }
+    }
+
+    #[test]
+    fn signed_hex_str_serialize() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct WithSignedHexStr {
+            val: HexStr<i32>,
+        }
+
+        let params = WithSignedHexStr {
+            val: HexStr {
+                val: -1,
+                add_0x_with_encoding: true,
+                ..Default::default()
+            },
+        };
+        let s: String<32> = to_string(&params, "+CMD", SerializeOptions::default()).unwrap();
+        assert_eq!(
+            s,
+            String::<32>::try_from("AT+CMD=\"0xFFFFFFFF\"\r").unwrap()
+        );
+
+        let val: HexStr<i32> = crate::from_str("+CMD: 0xFFFFFFFF").unwrap();
+        assert_eq!(val.val, params.val.val);
+    }
+
+    #[test]
+    fn reverse_fields() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct TwoParams<'a> {
+            a: &'a str,
+            b: u16,
+        }
+
+        let params = TwoParams { a: "abc", b: 300 };
+        let options = SerializeOptions {
+            reverse_fields: true,
+            ..Default::default()
+        };
+
+        let s: String<32> = to_string(&params, "+CMD", options).unwrap();
+        assert_eq!(s, String::<32>::try_from("AT+CMD=300,\"abc\"\r").unwrap());
+
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct ThreeParams {
+            a: u8,
+            b: Option<u8>,
+            c: u8,
+        }
+
+        let params = ThreeParams {
+            a: 1,
+            b: None,
+            c: 3,
+        };
+        let options = SerializeOptions {
+            reverse_fields: true,
+            ..Default::default()
+        };
+        let s: String<32> = to_string(&params, "+CMD", options).unwrap();
+        assert_eq!(s, String::<32>::try_from("AT+CMD=3,,1\r").unwrap());
+    }
+
+    #[test]
+    fn hex_caps_option() {
+        #[derive(Clone, PartialEq, Serialize)]
+        pub struct WithHexStr {
+            default_case: HexStr<u16>,
+            small_case: HexStr<u16>,
+            caps: HexStr<u16>,
+        }
+
+        let params = WithHexStr {
+            default_case: HexStr {
+                val: 0xAB,
+                add_0x_with_encoding: true,
+                ..Default::default()
+            },
+            small_case: HexStr {
+                val: 0xCD,
+                hex_in_caps: false,
+                ..Default::default()
+            },
+            caps: HexStr {
+                val: 0xEF,
+                fixed_case: true,
+                ..Default::default()
+            },
+        };
+
+        let s: String<32> = to_string(&params, "+CMD", SerializeOptions::default()).unwrap();
+        assert_eq!(
+            s,
+            String::<32>::try_from("AT+CMD=\"0xAB\",\"cd\",\"EF\"\r").unwrap()
+        );
+
+        // The global default applies to values left at the default case, while
+        // explicitly set ones are kept
+        let options = SerializeOptions {
+            hex_caps: Some(false),
+            ..Default::default()
+        };
+        let s: String<32> = to_string(&params, "+CMD", options).unwrap();
+        assert_eq!(
+            s,
+            String::<32>::try_from("AT+CMD=\"0xab\",\"cd\",\"EF\"\r").unwrap()
+        );
+
+        // Values explicitly in small case are kept as such
+        let options = SerializeOptions {
+            hex_caps: Some(true),
+            ..Default::default()
+        };
+        let s: String<32> = to_string(&params, "+CMD", options).unwrap();
+        assert_eq!(
+            s,
+            String::<32>::try_from("AT+CMD=\"0xAB\",\"cd\",\"EF\"\r").unwrap()
+        );
+    }
+
     #[test]
     fn serialize_string_with_escape_sequences() {
         #[derive(Clone, PartialEq, Serialize)]
@@ -1121,6 +1700,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: true,
+                fixed_case: false,
             },
             val_0x_caps_array: HexStr {
                 val: [0xFF, 0x00, 0xAA, 0x55, 0xFF, 0x00, 0xAA, 0x55],
@@ -1129,6 +1709,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_0x_small_caps_array: HexStr {
                 val: [0xFF, 0x00, 0xAA, 0x55, 0xFF, 0x00, 0xAA, 0x55],
@@ -1137,6 +1718,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_no_0x_caps_array: HexStr {
                 val: [0xFF, 0x00, 0xAA, 0x55, 0xFF, 0x00, 0xAA, 0x55],
@@ -1145,6 +1727,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_no_0x_small_caps_array: HexStr {
                 val: [0xFF, 0x00, 0xAA, 0x55, 0xFF, 0x00, 0xAA, 0x55],
@@ -1153,6 +1736,7 @@ mod tests {
                 delimiter: ' ',
                 delimiter_after_nibble_count: 0,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_0x_caps_array_delimiter: HexStr {
                 val: [0xFF, 0x00, 0xAA, 0x55, 0xFF, 0x00, 0xAA, 0x55],
@@ -1161,6 +1745,7 @@ mod tests {
                 delimiter: '-',
                 delimiter_after_nibble_count: 2,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_0x_small_caps_array_delimiter: HexStr {
                 val: [0xFF, 0x00, 0xAA, 0x55, 0xFF, 0x00, 0xAA, 0x55],
@@ -1169,6 +1754,7 @@ mod tests {
                 delimiter: ':',
                 delimiter_after_nibble_count: 1,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_no_0x_caps_array_delimiter: HexStr {
                 val: [0xFF, 0x00, 0xAA, 0x55, 0xFF, 0x00, 0xAA, 0x55],
@@ -1177,6 +1763,7 @@ mod tests {
                 delimiter: '_',
                 delimiter_after_nibble_count: 2,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
             val_no_0x_small_caps_array_delimiter: HexStr {
                 val: [0xFF, 0x00, 0xAA, 0x55, 0xFF, 0x00, 0xAA, 0x55],
@@ -1185,6 +1772,7 @@ mod tests {
                 delimiter: '#',
                 delimiter_after_nibble_count: 1,
                 skip_last_0_values: false,
+                fixed_case: false,
             },
         };
         let s: String<600> = to_string(&params, "+CMD", options).unwrap();
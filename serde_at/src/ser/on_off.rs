@@ -0,0 +1,12 @@
+use crate::OnOff;
+use serde::ser::Serialize;
+use serde::Serializer;
+
+impl Serialize for OnOff {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u8(u8::from(self.0))
+    }
+}
@@ -0,0 +1,38 @@
+use core::ops::{Deref, DerefMut};
+use serde::ser::Serialize;
+use serde::Serializer;
+
+/// `RawBytes<N>`
+/// A byte vector that is serialized verbatim, rather than as a sequence of
+/// numbers. Can be dereferenced to the inner `heapless::Vec<u8, N>`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RawBytes<const N: usize>(pub heapless::Vec<u8, N>);
+
+impl<const N: usize> Serialize for RawBytes<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<const N: usize> From<heapless::Vec<u8, N>> for RawBytes<N> {
+    fn from(v: heapless::Vec<u8, N>) -> Self {
+        Self(v)
+    }
+}
+
+impl<const N: usize> Deref for RawBytes<N> {
+    type Target = heapless::Vec<u8, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> DerefMut for RawBytes<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -7,6 +7,7 @@ pub struct SerializeStruct<'a, 'b> {
     nested: bool,
     first: bool,
     last_some_written: usize,
+    fields_start: usize,
 }
 
 impl<'a, 'b> SerializeStruct<'a, 'b> {
@@ -17,6 +18,7 @@ impl<'a, 'b> SerializeStruct<'a, 'b> {
             nested,
             first: true,
             last_some_written,
+            fields_start: last_some_written,
         }
     }
 }
@@ -29,10 +31,13 @@ impl<'a, 'b> ser::SerializeStruct for SerializeStruct<'a, 'b> {
     where
         T: ser::Serialize + ?Sized,
     {
-        if self.first {
-            if !self.nested && self.ser.options.value_sep {
+        let reverse = !self.nested && self.ser.options.reverse_fields;
+        let first = self.first;
+        if first {
+            if !self.nested && self.ser.options.value_sep && !self.ser.cmd.is_empty() {
                 self.ser.push(b'=')?;
             }
+            self.fields_start = self.ser.written;
         } else {
             self.ser.push(b',')?;
         }
@@ -40,7 +45,16 @@ impl<'a, 'b> ser::SerializeStruct for SerializeStruct<'a, 'b> {
 
         let before = self.ser.written;
         value.serialize(&mut *self.ser)?;
-        if self.ser.written > before {
+        if reverse {
+            // Move the field ahead of the previous ones: `a,b` becomes `b,a`
+            if !first {
+                let value_len = self.ser.written - before;
+                let fields = &mut self.ser.buf[self.fields_start..self.ser.written];
+                fields.rotate_right(value_len);
+                fields[value_len..].rotate_right(1);
+            }
+            self.last_some_written = self.ser.written;
+        } else if self.ser.written > before {
             self.last_some_written = self.ser.written;
         }
         Ok(())
@@ -49,8 +63,7 @@ impl<'a, 'b> ser::SerializeStruct for SerializeStruct<'a, 'b> {
     fn end(self) -> Result<Self::Ok> {
         if !self.nested {
             self.ser.written = self.last_some_written;
-            self.ser
-                .extend_from_slice(self.ser.options.termination.as_bytes())?;
+            self.ser.terminate()?;
         }
         Ok(())
     }